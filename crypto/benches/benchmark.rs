@@ -6,7 +6,6 @@ use crypto::{
 
 fn random_matrix(poly_length: usize, num_polynomials: usize) -> Vec<Polynomial> {
     (0..num_polynomials)
-        .into_iter()
         .map(|_| random_polynomial(poly_length))
         .collect()
 }
@@ -25,7 +24,7 @@ pub fn criterion_benchmark(c: &mut Criterion) {
 
                 (agg, pp.commit_key, domain)
             },
-            |(agg, commit_key, domain)| agg.create(&commit_key, &domain),
+            |(agg, commit_key, domain)| black_box(agg.create(&commit_key, &domain)),
         )
     });
 }
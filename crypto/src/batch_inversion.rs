@@ -1,5 +1,7 @@
 use crate::Scalar;
 use ff::Field;
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
 
 // Batch inversion of multiple elements
 // This method will panic if one of the elements is zero
@@ -7,6 +9,13 @@ pub(crate) fn batch_inverse(elements: &mut [Scalar]) {
     batch_inversion(elements)
 }
 
+// Batch inversion which keeps its partial products in `scratch`,
+// so that it does not allocate once `scratch` is large enough.
+// This method will panic if one of the elements is zero
+pub(crate) fn batch_inverse_with_scratch(elements: &mut [Scalar], scratch: &mut Vec<Scalar>) {
+    serial_batch_inversion(elements, scratch)
+}
+
 // Taken from arkworks codebase
 // Given a vector of field elements {v_i}, compute the vector {coeff * v_i^(-1)}
 #[cfg(feature = "rayon")]
@@ -19,19 +28,21 @@ fn batch_inversion(v: &mut [Scalar]) {
         std::cmp::max(num_elems / num_cpus_available, min_elements_per_thread);
 
     // Batch invert in parallel, without copying the vector
-    v.par_chunks_mut(num_elem_per_thread).for_each(|mut chunk| {
-        serial_batch_inversion(&mut chunk);
+    v.par_chunks_mut(num_elem_per_thread).for_each(|chunk| {
+        serial_batch_inversion(chunk, &mut Vec::with_capacity(chunk.len()));
     });
 }
 
 #[cfg(not(feature = "rayon"))]
 fn batch_inversion(v: &mut [Scalar]) {
-    serial_batch_inversion(v);
+    serial_batch_inversion(v, &mut Vec::with_capacity(v.len()));
 }
 
 /// Given a vector of field elements {v_i}, compute the vector {coeff * v_i^(-1)}
 /// This method is explicitly single core.
-fn serial_batch_inversion(v: &mut [Scalar]) {
+///
+/// `prod` is used to store the partial products, its previous contents are discarded.
+fn serial_batch_inversion(v: &mut [Scalar], prod: &mut Vec<Scalar>) {
     use std::ops::MulAssign;
 
     // Montgomery’s Trick and Fast Implementation of Masked AES
@@ -40,7 +51,7 @@ fn serial_batch_inversion(v: &mut [Scalar]) {
     // but with an optimization to multiply every element in the returned vector by coeff
 
     // First pass: compute [a, ab, abc, ...]
-    let mut prod = Vec::with_capacity(v.len());
+    prod.clear();
     let mut tmp = Scalar::one();
     for f in v.iter().filter(|f| !f.is_zero_vartime()) {
         tmp.mul_assign(f);
@@ -60,11 +71,11 @@ fn serial_batch_inversion(v: &mut [Scalar]) {
         // Ignore normalized elements
         .filter(|f| !f.is_zero_vartime())
         // Backwards, skip last element, fill in one for last term.
-        .zip(prod.into_iter().rev().skip(1).chain(Some(Scalar::one())))
+        .zip(prod.iter().rev().skip(1).chain(Some(&Scalar::one())))
     {
         // tmp := tmp * f; f := tmp * s = 1/f
         let new_tmp = tmp * *f;
-        *f = tmp * &s;
+        *f = tmp * s;
         tmp = new_tmp;
     }
 }
@@ -1,6 +1,21 @@
 use crate::{G1Point, G1Projective, Scalar};
 use ff::{Field, PrimeField};
 use group::{prime::PrimeCurveAffine, Curve, Group};
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+use std::ops::{Add, Mul, Sub};
+
+/// The coset generator given to `Domain::with_coset_generator` was zero
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ZeroCosetGenerator;
+
+impl std::fmt::Display for ZeroCosetGenerator {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "the coset generator must not be zero")
+    }
+}
+
+impl std::error::Error for ZeroCosetGenerator {}
 
 #[derive(Debug, Clone)]
 pub struct Domain {
@@ -16,6 +31,19 @@ pub struct Domain {
     // Inverse of the generator
     // This is useful for IFFT
     pub generator_inv: Scalar,
+    // The first `domain_size / 2` powers of the generator, in natural order.
+    // These are the twiddle factors for the FFT
+    pub(crate) twiddle_factors: Vec<Scalar>,
+    // The first `domain_size / 2` powers of the inverse generator,
+    // which are the twiddle factors for the IFFT
+    pub(crate) twiddle_factors_inv: Vec<Scalar>,
+    // The element which shifts the domain to the coset used by the coset FFT
+    pub(crate) coset_generator: Scalar,
+    // Inverse of the coset generator
+    pub(crate) coset_generator_inv: Scalar,
+    // Whether `roots` are in bit-reversed order, rather than in their natural order.
+    // The FFT takes and returns evaluations in the order of the roots
+    pub(crate) roots_bit_reversed: bool,
 }
 
 impl Domain {
@@ -43,15 +71,69 @@ impl Domain {
             roots.push(prev_root * generator)
         }
 
+        let twiddle_factors = roots[..size / 2].to_vec();
+        let twiddle_factors_inv = powers(generator_inv, size / 2);
+
+        let coset_generator = Scalar::multiplicative_generator();
+        let coset_generator_inv = coset_generator.invert().unwrap();
+
         Self {
             roots,
             domain_size: size_as_scalar,
             domain_size_inv: size_as_scalar_inv,
             generator,
             generator_inv,
+            twiddle_factors,
+            twiddle_factors_inv,
+            coset_generator,
+            coset_generator_inv,
+            roots_bit_reversed: false,
         }
     }
 
+    /// Bit-reverses the order of the roots, as in EIP-4844.
+    ///
+    /// The FFT methods then take and return evaluations in the new order of the roots.
+    /// The order should only be changed through this method, since the FFT does not
+    /// look at the roots to find out their order.
+    pub fn bit_reverse_roots(mut self) -> Domain {
+        bit_reversal_permutation(&mut self.roots);
+        self.roots_bit_reversed = !self.roots_bit_reversed;
+        self
+    }
+
+    /// Returns true if the roots are in bit-reversed order, rather than in their natural order
+    pub fn is_bit_reversed(&self) -> bool {
+        self.roots_bit_reversed
+    }
+
+    /// Changes the element which the coset FFT shifts the domain by.
+    ///
+    /// By default this is the multiplicative generator of the scalar field, which is not
+    /// in any of the subgroups, so the coset does not overlap with the domain.
+    ///
+    /// Returns an error if the coset generator is zero, since it must be invertible.
+    pub fn with_coset_generator(
+        mut self,
+        coset_generator: Scalar,
+    ) -> Result<Domain, ZeroCosetGenerator> {
+        let coset_generator_inv =
+            Option::from(coset_generator.invert()).ok_or(ZeroCosetGenerator)?;
+        self.coset_generator = coset_generator;
+        self.coset_generator_inv = coset_generator_inv;
+        Ok(self)
+    }
+
+    /// Returns the element which the coset FFT shifts the domain by
+    pub fn coset_generator(&self) -> Scalar {
+        self.coset_generator
+    }
+
+    /// Returns the elements of the coset `coset_generator * roots`, in the same order as `roots`
+    pub fn coset_elements(&self) -> impl ExactSizeIterator<Item = Scalar> + '_ {
+        self.roots.iter().map(|root| self.coset_generator * root)
+    }
+
     fn largest_root_of_unity() -> Scalar {
         Scalar::from_str_vartime(
             "10238227357739495823651030575849232062558860180284477541189508159991286009131",
@@ -70,7 +152,7 @@ impl Domain {
         // We now want to compute the generator which has order `size`
         let exponent: u64 = 1 << (Domain::two_adicity() as u64 - log_size_of_group as u64);
 
-        Domain::largest_root_of_unity().pow_vartime(&[exponent])
+        Domain::largest_root_of_unity().pow_vartime([exponent])
     }
 
     const fn two_adicity() -> u32 {
@@ -89,29 +171,140 @@ impl Domain {
         &self.roots
     }
 
-    pub(crate) fn ifft_g1(&self, points: Vec<G1Point>) -> Vec<G1Point> {
-        if points.len() != self.size() {
+    /// Evaluates the polynomial with the given coefficients over the domain.
+    ///
+    /// The evaluations are in the same order as `roots`, which can either be in
+    /// their natural order, or in bit-reversed order as in EIP-4844.
+    ///
+    /// If there are fewer coefficients than the size of the domain, the rest are taken to be zero.
+    pub fn fft_scalars(&self, coefficients: Vec<Scalar>) -> Vec<Scalar> {
+        let mut evaluations = coefficients;
+        self.pad_coefficients(&mut evaluations, Scalar::zero());
+
+        fft_in_place(&mut evaluations, &self.twiddle_factors);
+        self.to_roots_order(&mut evaluations);
+        evaluations
+    }
+
+    /// Interpolates the polynomial with the given evaluations over the domain,
+    /// and returns its coefficients. This is the inverse of `fft_scalars`.
+    ///
+    /// The evaluations must be in the same order as `roots`.
+    pub fn ifft_scalars(&self, evaluations: Vec<Scalar>) -> Vec<Scalar> {
+        self.check_num_evaluations(evaluations.len());
+
+        let mut coefficients = evaluations;
+        self.to_roots_order(&mut coefficients);
+        self.ifft_in_place(&mut coefficients);
+        coefficients
+    }
+
+    /// The same as `fft_scalars`, but the coefficients are group elements
+    pub fn fft_g1(&self, points: Vec<G1Point>) -> Vec<G1Point> {
+        let mut points_proj: Vec<_> = points.into_iter().map(G1Projective::from).collect();
+        self.pad_coefficients(&mut points_proj, G1Projective::identity());
+
+        fft_in_place(&mut points_proj, &self.twiddle_factors);
+        self.to_roots_order(&mut points_proj);
+        batch_normalize(&points_proj)
+    }
+
+    /// The same as `ifft_scalars`, but the evaluations are group elements
+    pub fn ifft_g1(&self, points: Vec<G1Point>) -> Vec<G1Point> {
+        self.check_num_evaluations(points.len());
+
+        let mut points_proj: Vec<_> = points.into_iter().map(G1Projective::from).collect();
+        self.to_roots_order(&mut points_proj);
+        self.ifft_in_place(&mut points_proj);
+        batch_normalize(&points_proj)
+    }
+
+    /// Evaluates the polynomial with the given coefficients over the coset of the domain.
+    ///
+    /// The evaluations are in the same order as `coset_elements`.
+    ///
+    /// If there are fewer coefficients than the size of the domain, the rest are taken to be zero.
+    pub fn coset_fft_scalars(&self, coefficients: Vec<Scalar>) -> Vec<Scalar> {
+        let mut evaluations = coefficients;
+        self.pad_coefficients(&mut evaluations, Scalar::zero());
+
+        // f(g * x) is the polynomial whose i'th coefficient is f_i * g^i
+        distribute_powers(&mut evaluations, self.coset_generator);
+        fft_in_place(&mut evaluations, &self.twiddle_factors);
+        self.to_roots_order(&mut evaluations);
+        evaluations
+    }
+
+    /// Interpolates the polynomial with the given evaluations over the coset of the domain,
+    /// and returns its coefficients. This is the inverse of `coset_fft_scalars`.
+    ///
+    /// The evaluations must be in the same order as `coset_elements`.
+    pub fn coset_ifft_scalars(&self, evaluations: Vec<Scalar>) -> Vec<Scalar> {
+        self.check_num_evaluations(evaluations.len());
+
+        let mut coefficients = evaluations;
+        self.to_roots_order(&mut coefficients);
+        self.ifft_in_place(&mut coefficients);
+        distribute_powers(&mut coefficients, self.coset_generator_inv);
+        coefficients
+    }
+
+    /// The same as `coset_fft_scalars`, but the coefficients are group elements
+    pub fn coset_fft_g1(&self, points: Vec<G1Point>) -> Vec<G1Point> {
+        let mut points_proj: Vec<_> = points.into_iter().map(G1Projective::from).collect();
+        self.pad_coefficients(&mut points_proj, G1Projective::identity());
+
+        distribute_powers(&mut points_proj, self.coset_generator);
+        fft_in_place(&mut points_proj, &self.twiddle_factors);
+        self.to_roots_order(&mut points_proj);
+        batch_normalize(&points_proj)
+    }
+
+    /// The same as `coset_ifft_scalars`, but the evaluations are group elements
+    pub fn coset_ifft_g1(&self, points: Vec<G1Point>) -> Vec<G1Point> {
+        self.check_num_evaluations(points.len());
+
+        let mut points_proj: Vec<_> = points.into_iter().map(G1Projective::from).collect();
+        self.to_roots_order(&mut points_proj);
+        self.ifft_in_place(&mut points_proj);
+        distribute_powers(&mut points_proj, self.coset_generator_inv);
+        batch_normalize(&points_proj)
+    }
+
+    // The inverse FFT, where the evaluations are in natural order
+    // regardless of the order of `roots`
+    pub(crate) fn ifft_in_place<T: FftElement>(&self, values: &mut [T]) {
+        fft_in_place(values, &self.twiddle_factors_inv);
+        scale(values, self.domain_size_inv);
+    }
+
+    // Converts between natural order and the order of `roots`.
+    // Bit-reversing is its own inverse, so this converts in both directions.
+    pub(crate) fn to_roots_order<T>(&self, values: &mut [T]) {
+        if self.is_bit_reversed() {
+            bit_reversal_permutation(values)
+        }
+    }
+
+    fn pad_coefficients<T: Clone>(&self, coefficients: &mut Vec<T>, zero: T) {
+        if coefficients.len() > self.size() {
             panic!(
-                "number of points {}, must equal the domain size {}",
-                points.len(),
+                "number of coefficients {}, must not exceed the domain size {}",
+                coefficients.len(),
                 self.size()
             )
         }
+        coefficients.resize(self.size(), zero);
+    }
 
-        let points_proj: Vec<_> = points
-            .into_iter()
-            .map(|point_aff| G1Projective::from(point_aff))
-            .collect();
-
-        let mut ifft_g1 = fft_g1(self.generator_inv, &points_proj);
-
-        for element in ifft_g1.iter_mut() {
-            *element = *element * self.domain_size_inv
+    fn check_num_evaluations(&self, num_evaluations: usize) {
+        if num_evaluations != self.size() {
+            panic!(
+                "number of evaluations {}, must equal the domain size {}",
+                num_evaluations,
+                self.size()
+            )
         }
-
-        let mut affine = vec![G1Point::identity(); ifft_g1.len()];
-        G1Projective::batch_normalize(&ifft_g1, &mut affine);
-        return affine;
     }
 }
 
@@ -123,52 +316,487 @@ impl std::ops::Index<usize> for &Domain {
     }
 }
 
-fn fft_g1(nth_root_of_unity: Scalar, points: &[G1Projective]) -> Vec<G1Projective> {
-    let n = points.len();
-    if n == 1 {
-        return points.to_vec();
+pub(crate) fn batch_normalize(points: &[G1Projective]) -> Vec<G1Point> {
+    let mut affine = vec![G1Point::identity(); points.len()];
+    G1Projective::batch_normalize(points, &mut affine);
+    affine
+}
+
+// Permutes `values` in place, so that the value at index `i`
+// moves to the index whose bits are the reverse of `i`'s
+pub(crate) fn bit_reversal_permutation<T>(values: &mut [T]) {
+    let n = values.len();
+    let num_bits = n.trailing_zeros();
+    if num_bits == 0 {
+        return;
+    }
+
+    for i in 0..n {
+        let reversed = i.reverse_bits() >> (usize::BITS - num_bits);
+        if i < reversed {
+            values.swap(i, reversed)
+        }
+    }
+}
+
+fn powers(base: Scalar, num_powers: usize) -> Vec<Scalar> {
+    let mut powers = Vec::with_capacity(num_powers);
+    let mut power = Scalar::one();
+    for _ in 0..num_powers {
+        powers.push(power);
+        power *= base;
     }
+    powers
+}
 
-    let (even, odd) = take_even_odd(points);
+/// The elements that an FFT can be computed over, which are scalars and group elements
+pub(crate) trait FftElement:
+    Copy + Send + Sync + Add<Output = Self> + Sub<Output = Self> + Mul<Scalar, Output = Self>
+{
+}
 
-    // Compute a root with half the order
-    let gen_squared = nth_root_of_unity.square();
+impl<T> FftElement for T where
+    T: Copy + Send + Sync + Add<Output = T> + Sub<Output = T> + Mul<Scalar, Output = T>
+{
+}
 
-    let fft_even = fft_g1(gen_squared, &even);
-    let fft_odd = fft_g1(gen_squared, &odd);
+// An iterative radix-2 FFT, which replaces the coefficients in `values`
+// with their evaluations, in natural order.
+//
+// `twiddle_factors` are the first `values.len() / 2` powers of the root of unity
+// that the polynomial is evaluated at the powers of.
+fn fft_in_place<T: FftElement>(values: &mut [T], twiddle_factors: &[Scalar]) {
+    let n = values.len();
+    assert_eq!(
+        twiddle_factors.len(),
+        n / 2,
+        "the number of twiddle factors must be half the number of values"
+    );
+
+    // Reordering the coefficients lets the butterflies combine adjacent halves,
+    // and leaves the evaluations in natural order
+    bit_reversal_permutation(values);
+
+    // Each stage combines pairs of FFTs of size `half_size`, into FFTs of twice that size
+    let mut half_size = 1;
+    while half_size < n {
+        // The twiddle factors for this size are every `stride`th power of the root
+        let stride = n / (2 * half_size);
+        fft_stage(values, half_size, stride, twiddle_factors);
+        half_size *= 2;
+    }
+}
 
-    let mut input_point = Scalar::one();
-    let mut evaluations = vec![G1Projective::identity(); n];
+// Below this many values, spreading an FFT stage across threads costs more than it saves
+#[cfg(feature = "rayon")]
+const MIN_PARALLEL_FFT_SIZE: usize = 1 << 10;
+// The fewest butterflies that a single rayon task is given, when a chunk is split across threads
+#[cfg(feature = "rayon")]
+const MIN_BUTTERFLIES_PER_TASK: usize = 1 << 7;
+
+#[cfg(feature = "rayon")]
+fn fft_stage<T: FftElement>(
+    values: &mut [T],
+    half_size: usize,
+    stride: usize,
+    twiddle_factors: &[Scalar],
+) {
+    let chunk_size = 2 * half_size;
+    let num_chunks = values.len() / chunk_size;
+
+    if values.len() < MIN_PARALLEL_FFT_SIZE {
+        serial_fft_stage(values, half_size, stride, twiddle_factors)
+    } else if num_chunks >= rayon::current_num_threads() {
+        // The early stages have many small chunks, so each thread takes whole chunks
+        values
+            .par_chunks_mut(chunk_size)
+            .for_each(|chunk| chunk_butterflies(chunk, half_size, stride, twiddle_factors));
+    } else {
+        // The late stages have a few large chunks, so the butterflies within each chunk
+        // are spread across threads instead
+        for chunk in values.chunks_mut(chunk_size) {
+            let (low, high) = chunk.split_at_mut(half_size);
+            low.par_iter_mut()
+                .zip(high.par_iter_mut())
+                .enumerate()
+                .with_min_len(MIN_BUTTERFLIES_PER_TASK)
+                .for_each(|(j, (low, high))| butterfly(low, high, j * stride, twiddle_factors));
+        }
+    }
+}
 
-    for k in 0..n / 2 {
-        let tmp = fft_odd[k] * input_point;
-        evaluations[k] = G1Projective::from(fft_even[k]) + tmp;
-        evaluations[k + n / 2] = fft_even[k] - tmp;
+#[cfg(not(feature = "rayon"))]
+fn fft_stage<T: FftElement>(
+    values: &mut [T],
+    half_size: usize,
+    stride: usize,
+    twiddle_factors: &[Scalar],
+) {
+    serial_fft_stage(values, half_size, stride, twiddle_factors)
+}
 
-        input_point = input_point * nth_root_of_unity;
+fn serial_fft_stage<T: FftElement>(
+    values: &mut [T],
+    half_size: usize,
+    stride: usize,
+    twiddle_factors: &[Scalar],
+) {
+    for chunk in values.chunks_mut(2 * half_size) {
+        chunk_butterflies(chunk, half_size, stride, twiddle_factors)
     }
+}
 
-    evaluations
+// Combines the two halves of `chunk`, which are FFTs of size `half_size`
+fn chunk_butterflies<T: FftElement>(
+    chunk: &mut [T],
+    half_size: usize,
+    stride: usize,
+    twiddle_factors: &[Scalar],
+) {
+    let (low, high) = chunk.split_at_mut(half_size);
+    for (j, (low, high)) in low.iter_mut().zip(high.iter_mut()).enumerate() {
+        butterfly(low, high, j * stride, twiddle_factors)
+    }
 }
-fn take_even_odd<T: Clone>(list: &[T]) -> (Vec<T>, Vec<T>) {
-    let mut even = Vec::with_capacity(list.len() / 2);
-    let mut odd = Vec::with_capacity(list.len() / 2);
 
-    for (index, value) in list.iter().enumerate() {
-        if index % 2 == 0 {
-            even.push(value.clone())
-        } else {
-            odd.push(value.clone())
-        }
+fn butterfly<T: FftElement>(
+    low: &mut T,
+    high: &mut T,
+    twiddle_index: usize,
+    twiddle_factors: &[Scalar],
+) {
+    // The first twiddle factor is one, so the multiplication can be skipped.
+    // This matters for group elements, where it is a scalar multiplication
+    let tmp = if twiddle_index == 0 {
+        *high
+    } else {
+        *high * twiddle_factors[twiddle_index]
+    };
+    *high = *low - tmp;
+    *low = *low + tmp;
+}
+
+#[cfg(feature = "rayon")]
+fn scale<T: FftElement>(values: &mut [T], factor: Scalar) {
+    values
+        .par_iter_mut()
+        .for_each(|value| *value = *value * factor);
+}
+
+#[cfg(not(feature = "rayon"))]
+fn scale<T: FftElement>(values: &mut [T], factor: Scalar) {
+    for value in values.iter_mut() {
+        *value = *value * factor
     }
+}
 
-    (even, odd)
+// Multiplies the value at index `i` by `base^i`
+#[cfg(feature = "rayon")]
+fn distribute_powers<T: FftElement>(values: &mut [T], base: Scalar) {
+    // Each chunk starts from its own power of the base, so the chunks can be done in parallel
+    const CHUNK_SIZE: usize = 1024;
+    values
+        .par_chunks_mut(CHUNK_SIZE)
+        .enumerate()
+        .for_each(|(chunk_index, chunk)| {
+            let mut power = base.pow_vartime([(chunk_index * CHUNK_SIZE) as u64]);
+            for value in chunk {
+                *value = *value * power;
+                power *= base;
+            }
+        });
 }
 
-#[test]
-fn largest_group_has_correct_order() {
-    let root = Domain::largest_root_of_unity();
-    let order = 2u64.pow(Domain::two_adicity());
+// Multiplies the value at index `i` by `base^i`
+#[cfg(not(feature = "rayon"))]
+fn distribute_powers<T: FftElement>(values: &mut [T], base: Scalar) {
+    let mut power = Scalar::one();
+    for value in values.iter_mut() {
+        *value = *value * power;
+        power *= base;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{random_g1, random_vector};
+
+    // The original recursive FFT, which the iterative FFT is checked against.
+    //
+    // Computes the evaluations of the polynomial with coefficients `values`
+    // at the powers of `nth_root_of_unity`, in natural order.
+    fn recursive_fft<T: FftElement>(nth_root_of_unity: Scalar, values: &[T]) -> Vec<T> {
+        let n = values.len();
+        if n == 1 {
+            return values.to_vec();
+        }
+
+        let (even, odd) = take_even_odd(values);
+
+        // Compute a root with half the order
+        let gen_squared = nth_root_of_unity.square();
+
+        let fft_even = recursive_fft(gen_squared, &even);
+        let fft_odd = recursive_fft(gen_squared, &odd);
+
+        let mut input_point = Scalar::one();
+        let mut evaluations = fft_even.clone();
+        evaluations.extend_from_slice(&fft_even);
+
+        for k in 0..n / 2 {
+            let tmp = fft_odd[k] * input_point;
+            evaluations[k] = fft_even[k] + tmp;
+            evaluations[k + n / 2] = fft_even[k] - tmp;
+
+            input_point *= nth_root_of_unity;
+        }
+
+        evaluations
+    }
+
+    fn take_even_odd<T: Clone>(list: &[T]) -> (Vec<T>, Vec<T>) {
+        let mut even = Vec::with_capacity(list.len() / 2);
+        let mut odd = Vec::with_capacity(list.len() / 2);
+
+        for (index, value) in list.iter().enumerate() {
+            if index % 2 == 0 {
+                even.push(value.clone())
+            } else {
+                odd.push(value.clone())
+            }
+        }
+
+        (even, odd)
+    }
+
+    #[test]
+    fn iterative_fft_matches_recursive_fft() {
+        // With the `parallel` feature, the larger sizes are spread across threads
+        for log_size in 0..=12 {
+            let domain = Domain::new(1 << log_size);
+
+            let scalars = random_vector(domain.size());
+            let mut iterative = scalars.clone();
+            fft_in_place(&mut iterative, &domain.twiddle_factors);
+            assert_eq!(iterative, recursive_fft(domain.generator, &scalars));
+
+            let mut iterative_inv = scalars.clone();
+            fft_in_place(&mut iterative_inv, &domain.twiddle_factors_inv);
+            assert_eq!(iterative_inv, recursive_fft(domain.generator_inv, &scalars));
+        }
+
+        // Group elements are much slower, so only a few sizes are checked
+        for log_size in 0..=4 {
+            let domain = Domain::new(1 << log_size);
+
+            let points: Vec<_> = (0..domain.size())
+                .map(|_| G1Projective::from(random_g1()))
+                .collect();
+            let mut iterative = points.clone();
+            fft_in_place(&mut iterative, &domain.twiddle_factors);
+            assert_eq!(iterative, recursive_fft(domain.generator, &points));
+        }
+    }
+
+    #[test]
+    fn largest_group_has_correct_order() {
+        let root = Domain::largest_root_of_unity();
+        let order = 2u64.pow(Domain::two_adicity());
+
+        assert_eq!(root.pow_vartime([order]), Scalar::one())
+    }
+
+    fn bit_reversed(domain: &Domain) -> Domain {
+        domain.clone().bit_reverse_roots()
+    }
+
+    #[test]
+    fn bit_reverse_roots_tracks_the_order() {
+        let natural = Domain::new(8);
+        assert!(!natural.is_bit_reversed());
 
-    assert_eq!(root.pow_vartime(&[order]), Scalar::one())
+        let bit_reversed = natural.clone().bit_reverse_roots();
+        assert!(bit_reversed.is_bit_reversed());
+        let mut expected_roots = natural.roots.clone();
+        bit_reversal_permutation(&mut expected_roots);
+        assert_eq!(bit_reversed.roots, expected_roots);
+
+        // Bit-reversing is its own inverse
+        let natural_again = bit_reversed.bit_reverse_roots();
+        assert!(!natural_again.is_bit_reversed());
+        assert_eq!(natural_again.roots, natural.roots);
+
+        // Both orders are the same for small domains, but the flag still records the order
+        assert!(Domain::new(2).bit_reverse_roots().is_bit_reversed());
+    }
+
+    // Evaluates the polynomial with `coefficients` at every root, in the order of the roots
+    fn naive_evaluations<T>(coefficients: &[T], domain: &Domain, zero: T) -> Vec<T>
+    where
+        T: Copy + Add<Output = T> + Mul<Scalar, Output = T>,
+    {
+        domain
+            .roots
+            .iter()
+            .map(|root| {
+                let mut result = zero;
+                let mut power = Scalar::one();
+                for coefficient in coefficients {
+                    result = result + *coefficient * power;
+                    power *= root;
+                }
+                result
+            })
+            .collect()
+    }
+
+    #[test]
+    fn fft_scalars_matches_naive_evaluation() {
+        for size in [1, 2, 4, 16] {
+            let natural = Domain::new(size);
+            for domain in [bit_reversed(&natural), natural] {
+                let coefficients = random_vector(size);
+
+                let evaluations = domain.fft_scalars(coefficients.clone());
+                assert_eq!(
+                    evaluations,
+                    naive_evaluations(&coefficients, &domain, Scalar::zero())
+                );
+                assert_eq!(domain.ifft_scalars(evaluations), coefficients);
+            }
+        }
+    }
+
+    #[test]
+    fn fft_scalars_pads_coefficients() {
+        let domain = bit_reversed(&Domain::new(16));
+        let coefficients = random_vector(5);
+
+        let evaluations = domain.fft_scalars(coefficients.clone());
+        assert_eq!(
+            evaluations,
+            naive_evaluations(&coefficients, &domain, Scalar::zero())
+        );
+
+        let mut padded = coefficients;
+        padded.resize(16, Scalar::zero());
+        assert_eq!(domain.ifft_scalars(evaluations), padded);
+    }
+
+    // Evaluates the polynomial with `coefficients` at every element of the coset
+    fn naive_coset_evaluations<T>(coefficients: &[T], domain: &Domain, zero: T) -> Vec<T>
+    where
+        T: Copy + Add<Output = T> + Mul<Scalar, Output = T>,
+    {
+        let coset = Domain {
+            roots: domain.coset_elements().collect(),
+            ..domain.clone()
+        };
+        naive_evaluations(coefficients, &coset, zero)
+    }
+
+    #[test]
+    fn coset_elements_do_not_overlap_the_domain() {
+        let domain = Domain::new(16);
+        assert_eq!(domain.coset_generator(), Scalar::from(7));
+
+        let coset: Vec<_> = domain.coset_elements().collect();
+        assert_eq!(coset.len(), domain.size());
+        for (element, root) in coset.iter().zip(&domain.roots) {
+            assert_eq!(*element, domain.coset_generator() * root);
+            assert!(domain.find(element).is_none());
+        }
+    }
+
+    #[test]
+    fn distribute_powers_multiplies_by_powers() {
+        // Large enough to be split into several chunks when computed in parallel
+        let base = Scalar::from(7);
+        let values = random_vector(3000);
+
+        let mut distributed = values.clone();
+        distribute_powers(&mut distributed, base);
+        let expected: Vec<_> = values
+            .iter()
+            .zip(powers(base, values.len()))
+            .map(|(value, power)| value * power)
+            .collect();
+        assert_eq!(distributed, expected);
+    }
+
+    #[test]
+    fn zero_coset_generator_is_rejected() {
+        assert_eq!(
+            Domain::new(4).with_coset_generator(Scalar::zero()).err(),
+            Some(ZeroCosetGenerator)
+        );
+    }
+
+    #[test]
+    fn coset_fft_scalars_matches_naive_evaluation() {
+        for size in [1, 2, 4, 16] {
+            let natural = Domain::new(size);
+            let custom = Domain::new(size)
+                .with_coset_generator(Scalar::from(5))
+                .unwrap();
+            for domain in [
+                bit_reversed(&natural),
+                bit_reversed(&custom),
+                natural,
+                custom,
+            ] {
+                let coefficients = random_vector(size);
+
+                let evaluations = domain.coset_fft_scalars(coefficients.clone());
+                assert_eq!(
+                    evaluations,
+                    naive_coset_evaluations(&coefficients, &domain, Scalar::zero())
+                );
+                assert_eq!(domain.coset_ifft_scalars(evaluations), coefficients);
+            }
+        }
+
+        // Fewer coefficients than the domain size are padded
+        let domain = bit_reversed(&Domain::new(16));
+        let coefficients = random_vector(5);
+        assert_eq!(
+            domain.coset_fft_scalars(coefficients.clone()),
+            naive_coset_evaluations(&coefficients, &domain, Scalar::zero())
+        );
+    }
+
+    #[test]
+    fn coset_fft_g1_matches_naive_evaluation() {
+        let size = 8;
+        let natural = Domain::new(size)
+            .with_coset_generator(Scalar::from(5))
+            .unwrap();
+        for domain in [bit_reversed(&natural), natural] {
+            let points: Vec<_> = (0..size).map(|_| random_g1()).collect();
+
+            let points_proj: Vec<_> = points.iter().map(G1Projective::from).collect();
+            let expected = naive_coset_evaluations(&points_proj, &domain, G1Projective::identity());
+
+            let evaluations = domain.coset_fft_g1(points.clone());
+            assert_eq!(evaluations, batch_normalize(&expected));
+            assert_eq!(domain.coset_ifft_g1(evaluations), points);
+        }
+    }
+
+    #[test]
+    fn fft_g1_matches_naive_evaluation() {
+        let size = 8;
+        let natural = Domain::new(size);
+        for domain in [bit_reversed(&natural), natural] {
+            let points: Vec<_> = (0..size).map(|_| random_g1()).collect();
+
+            let points_proj: Vec<_> = points.iter().map(G1Projective::from).collect();
+            let expected = naive_evaluations(&points_proj, &domain, G1Projective::identity());
+
+            let evaluations = domain.fft_g1(points.clone());
+            assert_eq!(evaluations, batch_normalize(&expected));
+            assert_eq!(domain.ifft_g1(evaluations), points);
+        }
+    }
 }
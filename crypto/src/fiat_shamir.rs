@@ -0,0 +1,84 @@
+use crate::Scalar;
+use sha2::{Digest, Sha256};
+
+/// A transcript is used to derive challenges non-interactively,
+/// using the Fiat-Shamir heuristic.
+///
+/// Everything that the prover has committed to, must be added
+/// to the transcript before the challenge is computed.
+#[derive(Debug, Clone)]
+pub struct Transcript {
+    hasher: Sha256,
+}
+
+impl Transcript {
+    /// Creates a new transcript.
+    ///
+    /// The domain separator ensures that challenges produced
+    /// for one protocol cannot be reused in another.
+    pub fn with_domain_separator(domain_separator: &[u8]) -> Transcript {
+        let mut hasher = Sha256::new();
+        hasher.update(domain_separator);
+        Transcript { hasher }
+    }
+
+    pub fn append_bytes(&mut self, bytes: &[u8]) {
+        self.hasher.update(bytes)
+    }
+
+    /// Hashes everything that was appended to the transcript
+    /// and returns the digest as a field element.
+    pub fn challenge(self) -> Scalar {
+        let digest: [u8; 32] = self.hasher.finalize().into();
+        reduce_bytes_to_scalar_be(&digest)
+    }
+}
+
+/// Interprets `bytes` as a big-endian integer and reduces it
+/// modulo the order of the scalar field.
+///
+/// Unlike `Scalar::from_bytes_be`, this method is infallible.
+pub fn reduce_bytes_to_scalar_be(bytes: &[u8; 32]) -> Scalar {
+    // 2^64 as a field element
+    let two_pow_64 = Scalar::from(u64::MAX) + Scalar::from(1u64);
+
+    // Horner's method over the 64 bit limbs, most significant limb first
+    let mut result = Scalar::from(0u64);
+    for limb in bytes.chunks_exact(8) {
+        let limb = u64::from_be_bytes(limb.try_into().expect("chunks are 8 bytes"));
+        result = result * two_pow_64 + Scalar::from(limb);
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ff::{Field, PrimeField};
+
+    #[test]
+    fn reduce_canonical_scalar_is_identity() {
+        let scalar = Scalar::random(&mut rand::thread_rng());
+        assert_eq!(reduce_bytes_to_scalar_be(&scalar.to_bytes_be()), scalar)
+    }
+
+    #[test]
+    fn reduce_non_canonical_bytes() {
+        // (2^256 - 1) mod r
+        let expected = Scalar::from_str_vartime(
+            "10920338887063814464675503992315976177888879664585288394250266608035967270909",
+        )
+        .unwrap();
+        assert_eq!(reduce_bytes_to_scalar_be(&[0xff; 32]), expected)
+    }
+
+    #[test]
+    fn challenge_depends_on_domain_separator() {
+        let mut transcript_a = Transcript::with_domain_separator(b"protocol a");
+        let mut transcript_b = Transcript::with_domain_separator(b"protocol b");
+        transcript_a.append_bytes(b"data");
+        transcript_b.append_bytes(b"data");
+
+        assert_ne!(transcript_a.challenge(), transcript_b.challenge())
+    }
+}
@@ -1,3 +1,4 @@
+pub mod aggregated_kzg;
 pub mod commit_key;
 pub mod opening_key;
 pub mod proof;
@@ -0,0 +1,131 @@
+/// This is the aggregated KZG proof algorithm.
+///
+/// Multiple polynomials are combined into a single polynomial
+/// using a random linear combination, and the combination is
+/// opened using a single KZG proof.
+use super::{
+    commit_key::{g1_lincomb, CommitKeyLagrange},
+    opening_key::OpeningKey,
+    proof::{KZGWitness, Proof},
+};
+use crate::{Domain, G1Point, Polynomial, Scalar, Transcript};
+use ff::Field;
+
+// Domain separator for the aggregated KZG protocol
+const DOMAIN_SEP: &[u8] = b"AGGREGATED_KZG_V1_";
+
+pub struct AggregatedKZG {
+    polys: Vec<Polynomial>,
+    poly_comms: Vec<G1Point>,
+}
+
+impl AggregatedKZG {
+    /// Panics if the number of polynomials does not match the number of commitments
+    pub fn from_polys(polys: Vec<Polynomial>, poly_comms: Vec<G1Point>) -> AggregatedKZG {
+        assert_eq!(
+            polys.len(),
+            poly_comms.len(),
+            "each polynomial must have exactly one commitment"
+        );
+        AggregatedKZG { polys, poly_comms }
+    }
+
+    /// Creates a single proof for all of the polynomials
+    pub fn create(&self, commit_key: &CommitKeyLagrange, domain: &Domain) -> KZGWitness {
+        let (aggregated_poly, aggregated_poly_comm, evaluation_challenge) =
+            self.compute_aggregated_poly_and_commitment();
+
+        let proof = Proof::create(
+            commit_key,
+            &aggregated_poly,
+            aggregated_poly_comm,
+            evaluation_challenge,
+            domain,
+        );
+
+        proof.quotient_commitment
+    }
+
+    /// Verifies a proof created by `AggregatedKZG::create`
+    pub fn verify(&self, witness: KZGWitness, opening_key: &OpeningKey, domain: &Domain) -> bool {
+        let (aggregated_poly, aggregated_poly_comm, evaluation_challenge) =
+            self.compute_aggregated_poly_and_commitment();
+
+        let output_point = aggregated_poly.evaluate(evaluation_challenge, domain);
+
+        opening_key.verify(
+            evaluation_challenge,
+            output_point,
+            aggregated_poly_comm,
+            witness,
+        )
+    }
+
+    // Computes the random linear combination of the polynomials and their commitments,
+    // along with the point that the aggregated polynomial will be evaluated at.
+    fn compute_aggregated_poly_and_commitment(&self) -> (Polynomial, G1Point, Scalar) {
+        let challenge = self.compute_challenge();
+
+        // Compute the powers [1, r, r^2, ..., r^{n-1}]
+        let num_polys = self.polys.len();
+        let mut r_powers = Vec::with_capacity(num_polys);
+        let mut current_power = Scalar::one();
+        for _ in 0..num_polys {
+            r_powers.push(current_power);
+            current_power *= challenge;
+        }
+        // The evaluation point is r^n, which is not used in the linear combination
+        let evaluation_challenge = current_power;
+
+        let aggregated_poly = Polynomial::linear_combination(&self.polys, &r_powers);
+        let aggregated_poly_comm = g1_lincomb(&self.poly_comms, &r_powers);
+
+        (aggregated_poly, aggregated_poly_comm, evaluation_challenge)
+    }
+
+    fn compute_challenge(&self) -> Scalar {
+        let mut transcript = Transcript::with_domain_separator(DOMAIN_SEP);
+
+        let num_polys = self.polys.len() as u64;
+        let degree_poly = self.polys.first().map_or(0, |poly| poly.evaluations.len()) as u64;
+        transcript.append_bytes(&degree_poly.to_be_bytes());
+        transcript.append_bytes(&num_polys.to_be_bytes());
+
+        for poly in &self.polys {
+            for evaluation in &poly.evaluations {
+                transcript.append_bytes(&evaluation.to_bytes_be());
+            }
+        }
+        for poly_comm in &self.poly_comms {
+            transcript.append_bytes(&poly_comm.to_compressed());
+        }
+
+        transcript.challenge()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{random_polynomial, test_setup};
+
+    #[test]
+    fn aggregated_proof_smoke() {
+        let size = 2usize.pow(4);
+        let num_polys = 4;
+        let (public_parameters, domain) = test_setup(size);
+
+        let polys: Vec<_> = (0..num_polys).map(|_| random_polynomial(size)).collect();
+        let poly_comms = public_parameters.commit_key.commit_multiple(&polys);
+
+        let agg = AggregatedKZG::from_polys(polys.clone(), poly_comms.clone());
+        let witness = agg.create(&public_parameters.commit_key, &domain);
+        assert!(agg.verify(witness, &public_parameters.opening_key, &domain));
+
+        // Changing one of the polynomials should invalidate the proof
+        let mut polys = polys;
+        polys[1] = random_polynomial(size);
+        let agg = AggregatedKZG::from_polys(polys, poly_comms);
+        assert!(!agg.verify(witness, &public_parameters.opening_key, &domain));
+    }
+}
@@ -1,6 +1,11 @@
-use crate::{domain::Domain, polynomial::Polynomial, G1Point, Scalar};
-#[cfg(feature = "rayon")]
-use rayon::prelude::*;
+use crate::{
+    domain::{batch_normalize, Domain},
+    polynomial::Polynomial,
+    workspace::MsmScratch,
+    G1Point, G1Projective, ProverWorkspace, Scalar,
+};
+use ff::PrimeField;
+use group::{prime::PrimeCurveAffine, Group};
 
 // The key that is used to commit to polynomials in monomial form
 //
@@ -22,9 +27,18 @@ impl CommitKey {
     }
     // Note: There is no commit method for CommitKey in monomial basis
     // as this is not used
+    //
+    // The lagrange points are in the same order as the roots of `domain`
     pub fn into_lagrange(self, domain: &Domain) -> CommitKeyLagrange {
+        let mut lagrange: Vec<_> = self.inner.into_iter().map(G1Projective::from).collect();
+
+        // The monomial points are not evaluations, so unlike `Domain::ifft_g1`,
+        // only the output is put in the order of the roots
+        domain.ifft_in_place(&mut lagrange);
+        domain.to_roots_order(&mut lagrange);
+
         CommitKeyLagrange {
-            inner: domain.ifft_g1(self.inner),
+            inner: batch_normalize(&lagrange),
         }
     }
 }
@@ -51,6 +65,23 @@ impl CommitKeyLagrange {
         g1_lincomb(&self.inner, &polynomial.evaluations)
     }
 
+    /// Commit to `polynomial` in lagrange form, using `workspace` instead of allocating
+    pub fn commit_with_workspace(
+        &self,
+        polynomial: &Polynomial,
+        workspace: &mut ProverWorkspace,
+    ) -> G1Point {
+        g1_lincomb_with_scratch(&self.inner, &polynomial.evaluations, &mut workspace.msm)
+    }
+
+    /// Commit to multiple polynomials in lagrange form
+    pub fn commit_multiple(&self, polynomials: &[Polynomial]) -> Vec<G1Point> {
+        polynomials
+            .iter()
+            .map(|polynomial| self.commit(polynomial))
+            .collect()
+    }
+
     /// Returns the maximum degree polynomial that one can commit to
     /// Since we are in lagrange basis, it is the number of points minus one
     ///
@@ -69,11 +100,9 @@ pub fn g1_lincomb(points: &[G1Point], scalars: &[Scalar]) -> G1Point {
     // TODO: Spec says we should panic, but as a lib its better to return result
     assert_eq!(points.len(), scalars.len());
 
-    let points_iter = points.into_iter();
+    let points_iter = points.iter();
 
-    let points: Vec<_> = points_iter
-        .map(|point| blstrs::G1Projective::from(point))
-        .collect();
+    let points: Vec<_> = points_iter.map(blstrs::G1Projective::from).collect();
 
     // blst does not use multiple threads
     // TODO: the internal lib seems to be converting back to Affine, so we need to
@@ -81,6 +110,58 @@ pub fn g1_lincomb(points: &[G1Point], scalars: &[Scalar]) -> G1Point {
     blstrs::G1Projective::multi_exp(&points, scalars).into()
 }
 
+// A multi-scalar multiplication, which keeps its buffers in `scratch` so that
+// it does not allocate once `scratch` is large enough.
+//
+// Unlike `g1_lincomb`, this is always single threaded.
+pub(crate) fn g1_lincomb_with_scratch(
+    points: &[G1Point],
+    scalars: &[Scalar],
+    scratch: &mut MsmScratch,
+) -> G1Point {
+    assert_eq!(points.len(), scalars.len());
+    if points.is_empty() {
+        return G1Point::identity();
+    }
+
+    scratch.scalar_bytes.clear();
+    for scalar in scalars {
+        scratch
+            .scalar_bytes
+            .extend_from_slice(&scalar.to_bytes_le());
+    }
+    scratch.reserve_buckets(points.len());
+
+    // blst treats a list of pointers whose second element is null,
+    // as a pointer to a contiguous array
+    let points_ptr: [*const blst::blst_p1_affine; 2] = [points[0].as_ref(), std::ptr::null()];
+    let scalars_ptr: [*const u8; 2] = [scratch.scalar_bytes.as_ptr(), std::ptr::null()];
+
+    let mut result = G1Projective::identity();
+    // SAFETY:
+    // - `G1Point` and `G1Projective` are `repr(transparent)` wrappers around `blst_p1_affine`
+    //   and `blst_p1`, so `points` can be read as a contiguous array of `blst_p1_affine`
+    //   and `result` is a valid `blst_p1` to write to.
+    // - The scalars are not passed as `Scalar`s, which are stored in Montgomery form.
+    //   They are serialized into `scalar_bytes` as 32 little-endian bytes each, which is the
+    //   layout of `blst_scalar`, so there are `points.len()` scalars of `NUM_BITS` bits.
+    // - `blst_p1s_mult_pippenger_scratch_sizeof(n)` returns the size of the scratch space
+    //   in bytes, and `reserve_buckets(points.len())` made `buckets` at least that large.
+    // - The pointers are only used for the duration of the call, and nothing else
+    //   borrows `points`, `scratch` or `result` while it runs.
+    unsafe {
+        blst::blst_p1s_mult_pippenger(
+            result.as_mut(),
+            points_ptr.as_ptr(),
+            points.len(),
+            scalars_ptr.as_ptr(),
+            Scalar::NUM_BITS as usize,
+            scratch.buckets.as_mut_ptr(),
+        );
+    }
+    result.into()
+}
+
 #[cfg(test)]
 mod tests {
     use ff::Field;
@@ -88,18 +169,11 @@ mod tests {
 
     use crate::{
         domain::Domain,
-        kzg::commit_key::{g1_lincomb, CommitKey},
-        G1Point, Scalar,
+        kzg::commit_key::{g1_lincomb, g1_lincomb_with_scratch, CommitKey},
+        workspace::MsmScratch,
+        G1Point, PolyCoeff, Scalar,
     };
 
-    fn eval_coeff_poly(poly: &[Scalar], input_point: &Scalar) -> Scalar {
-        let mut result = Scalar::zero();
-        for (index, coeff) in poly.iter().enumerate() {
-            result += input_point.pow_vartime(&[index as u64]) * coeff;
-        }
-        result
-    }
-
     #[test]
     fn transform_srs() {
         let degree = 16;
@@ -107,19 +181,19 @@ mod tests {
         let domain = Domain::new(degree);
 
         // f(x) -- These are the coefficients of the polynomial
-        let f_x_coeffs: Vec<_> = (0..degree as u64).into_iter().map(Scalar::from).collect();
+        let f_x_coeffs: Vec<_> = (0..degree as u64).map(Scalar::from).collect();
 
         // Evaluate f(x) over the domain -- To get the evaluation form of f(x)
         let f_x_evaluations: Vec<_> = domain
             .roots
             .iter()
-            .map(|root| eval_coeff_poly(&f_x_coeffs, root))
+            .map(|root| PolyCoeff::new(f_x_coeffs.clone()).evaluate(*root))
             .collect();
 
         let secret = Scalar::from(1234567u64);
         let monomial_srs: Vec<G1Point> = (0..degree)
             .map(|index| {
-                let secret_exp = secret.pow_vartime(&[index as u64]);
+                let secret_exp = secret.pow_vartime([index as u64]);
                 (G1Point::generator() * secret_exp).into()
             })
             .collect();
@@ -137,4 +211,54 @@ mod tests {
 
         assert_eq!(expected_commitment, got_commitment)
     }
+
+    #[test]
+    fn transform_srs_bit_reversed() {
+        let degree = 16;
+
+        // The lagrange points follow the order of the roots of the domain
+        let domain = Domain::new(degree).bit_reverse_roots();
+
+        let f_x_coeffs: Vec<_> = (0..degree as u64).map(Scalar::from).collect();
+        let f_x_evaluations = domain.fft_scalars(f_x_coeffs.clone());
+
+        let secret = Scalar::from(1234567u64);
+        let monomial_srs: Vec<G1Point> = (0..degree)
+            .map(|index| (G1Point::generator() * secret.pow_vartime([index as u64])).into())
+            .collect();
+        let expected_commitment = g1_lincomb(&monomial_srs, &f_x_coeffs);
+
+        let lagrange_srs = CommitKey {
+            inner: monomial_srs,
+        }
+        .into_lagrange(&domain)
+        .inner;
+        let got_commitment = g1_lincomb(&lagrange_srs, &f_x_evaluations);
+
+        assert_eq!(expected_commitment, got_commitment)
+    }
+
+    #[test]
+    fn lincomb_with_scratch_matches() {
+        // blst uses a different algorithm for a single point and for fewer than 32 points
+        let mut scratch = MsmScratch::default();
+        assert_eq!(
+            g1_lincomb_with_scratch(&[], &[], &mut scratch),
+            G1Point::identity()
+        );
+
+        for num_points in [1, 2, 31, 32, 100] {
+            let points: Vec<G1Point> = (0..num_points)
+                .map(|i| (G1Point::generator() * Scalar::from(i as u64 + 1)).into())
+                .collect();
+            let scalars: Vec<_> = (0..num_points)
+                .map(|i| -Scalar::from(i as u64 * 7 + 3))
+                .collect();
+
+            assert_eq!(
+                g1_lincomb_with_scratch(&points, &scalars, &mut scratch),
+                g1_lincomb(&points, &scalars)
+            );
+        }
+    }
 }
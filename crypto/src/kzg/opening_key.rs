@@ -1,5 +1,7 @@
+use super::commit_key::g1_lincomb;
 use crate::{G1Point, G2Point, Scalar};
 use blstrs::{Bls12, G2Prepared};
+use ff::Field;
 use pairing_lib::{group::Group, MillerLoopResult, MultiMillerLoop};
 
 /// Opening Key is used to verify opening proofs made about a committed polynomial.
@@ -55,4 +57,127 @@ impl OpeningKey {
 
         pairing.is_identity().into()
     }
+
+    /// Checks that each polynomial `p_i` was evaluated at the point `z_i` and returned `y_i`.
+    ///
+    /// The proofs are folded into a single pairing check using powers of `challenge`.
+    /// `challenge` must be chosen after the proofs are fixed, ie either sampled randomly
+    /// or derived using Fiat-Shamir, otherwise an invalid proof can be made to cancel out.
+    ///
+    /// Returns false if any of the proofs are invalid.
+    pub fn verify_batch(
+        &self,
+        input_points: &[Scalar],
+        output_points: &[Scalar],
+        poly_comms: &[G1Point],
+        witness_comms: &[G1Point],
+        challenge: Scalar,
+    ) -> bool {
+        let num_proofs = input_points.len();
+        assert_eq!(num_proofs, output_points.len());
+        assert_eq!(num_proofs, poly_comms.len());
+        assert_eq!(num_proofs, witness_comms.len());
+
+        // The pairing check below is trivially satisfied for an empty batch
+        if num_proofs == 0 {
+            return true;
+        }
+
+        let r_powers = compute_powers(challenge, num_proofs);
+
+        // We want to check that:
+        // e(sum r^i * proof_i, [tau]) == e(sum r^i * (C_i - [y_i]) + sum r^i * z_i * proof_i, [1])
+        let proof_lincomb = g1_lincomb(witness_comms, &r_powers);
+
+        let r_times_z: Vec<_> = r_powers
+            .iter()
+            .zip(input_points)
+            .map(|(r_i, z_i)| r_i * z_i)
+            .collect();
+        let proof_z_lincomb = g1_lincomb(witness_comms, &r_times_z);
+
+        // sum r^i * (C_i - [y_i]) = sum r^i * C_i - [sum r^i * y_i]
+        let comm_lincomb = g1_lincomb(poly_comms, &r_powers);
+        let r_times_y: Scalar = r_powers
+            .iter()
+            .zip(output_points)
+            .map(|(r_i, y_i)| r_i * y_i)
+            .sum();
+        let c_minus_y_lincomb = comm_lincomb - (self.g1_gen * r_times_y);
+
+        let lhs_g1 = -proof_lincomb;
+        let rhs_g1: G1Point = (c_minus_y_lincomb + proof_z_lincomb).into();
+
+        let pairing = Bls12::multi_miller_loop(&[
+            (&lhs_g1, &self.prepared_beta_g2),
+            (&rhs_g1, &self.prepared_g2),
+        ])
+        .final_exponentiation();
+
+        pairing.is_identity().into()
+    }
+}
+
+// Computes [1, x, x^2, ..., x^{n-1}]
+fn compute_powers(x: Scalar, n: usize) -> Vec<Scalar> {
+    let mut powers = Vec::with_capacity(n);
+    let mut current_power = Scalar::one();
+    for _ in 0..n {
+        powers.push(current_power);
+        current_power *= x;
+    }
+    powers
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        test_utils::{random_polynomial, test_setup},
+        Proof, Scalar,
+    };
+
+    #[test]
+    fn batch_verify_smoke() {
+        let size = 2usize.pow(4);
+        let num_proofs = 5;
+        let (public_parameters, domain) = test_setup(size);
+        let commit_key = &public_parameters.commit_key;
+        let opening_key = &public_parameters.opening_key;
+
+        let mut input_points = Vec::new();
+        let mut output_points = Vec::new();
+        let mut poly_comms = Vec::new();
+        let mut witness_comms = Vec::new();
+        for i in 0..num_proofs {
+            let poly = random_polynomial(size);
+            let poly_comm = commit_key.commit(&poly);
+            let input_point = Scalar::from(123456u64 + i);
+
+            let proof = Proof::create(commit_key, &poly, poly_comm, input_point, &domain);
+
+            input_points.push(input_point);
+            output_points.push(proof.output_point);
+            poly_comms.push(proof.polynomial_commitment);
+            witness_comms.push(proof.quotient_commitment);
+        }
+
+        let challenge = Scalar::from(987654321u64);
+        assert!(opening_key.verify_batch(
+            &input_points,
+            &output_points,
+            &poly_comms,
+            &witness_comms,
+            challenge
+        ));
+
+        // A single invalid proof should invalidate the whole batch
+        output_points[2] += Scalar::from(1u64);
+        assert!(!opening_key.verify_batch(
+            &input_points,
+            &output_points,
+            &poly_comms,
+            &witness_comms,
+            challenge
+        ));
+    }
 }
@@ -3,8 +3,12 @@
 ///
 /// It is left unmodified and is its own
 /// isolated module.
-use super::{commit_key::CommitKeyLagrange, opening_key::OpeningKey, quotient_poly};
-use crate::{Domain, G1Point, Polynomial, Scalar};
+use super::{
+    commit_key::{g1_lincomb_with_scratch, CommitKeyLagrange},
+    opening_key::OpeningKey,
+    quotient_poly,
+};
+use crate::{Domain, G1Point, Polynomial, ProverWorkspace, Scalar};
 
 // Commitment to the quotient polynomial
 pub type KZGWitness = G1Point;
@@ -41,6 +45,31 @@ impl Proof {
         }
     }
 
+    /// Creates a proof, using `workspace` instead of allocating.
+    ///
+    /// Unlike `Proof::create`, the commitment to the quotient is computed on a single thread.
+    pub fn create_with_workspace(
+        commit_key: &CommitKeyLagrange,
+        poly: &Polynomial,
+        poly_comm: G1Point,
+        input_point: Scalar,
+        domain: &Domain,
+        workspace: &mut ProverWorkspace,
+    ) -> Proof {
+        let output_point = poly.evaluate_with_workspace(input_point, domain, workspace);
+
+        quotient_poly::compute_with_workspace(poly, input_point, output_point, domain, workspace);
+
+        let quotient_comm =
+            g1_lincomb_with_scratch(&commit_key.inner, &workspace.quotient, &mut workspace.msm);
+
+        Proof {
+            polynomial_commitment: poly_comm,
+            quotient_commitment: quotient_comm,
+            output_point,
+        }
+    }
+
     pub fn verify(&self, input_point: Scalar, opening_key: &OpeningKey) -> bool {
         opening_key.verify(
             input_point,
@@ -55,6 +84,50 @@ impl Proof {
 mod tests {
     use super::*;
     use crate::test_utils::{random_polynomial, test_setup};
+    use ff::Field;
+
+    fn bit_reverse<T: Copy>(values: &[T]) -> Vec<T> {
+        let num_bits = values.len().trailing_zeros();
+        (0..values.len())
+            .map(|i| values[i.reverse_bits() >> (usize::BITS - num_bits)])
+            .collect()
+    }
+
+    // Opens `poly` at every element of the domain, with and without a workspace
+    fn open_at_every_domain_element(
+        commit_key: &CommitKeyLagrange,
+        opening_key: &OpeningKey,
+        poly: &Polynomial,
+        domain: &Domain,
+    ) {
+        let poly_comm = commit_key.commit(poly);
+        let mut workspace = ProverWorkspace::default();
+
+        for (index, input_point) in domain.roots.iter().copied().enumerate() {
+            let proof = Proof::create(commit_key, poly, poly_comm, input_point, domain);
+            assert_eq!(proof.output_point, poly.evaluations[index]);
+            assert!(proof.verify(input_point, opening_key));
+
+            let workspace_proof = Proof::create_with_workspace(
+                commit_key,
+                poly,
+                poly_comm,
+                input_point,
+                domain,
+                &mut workspace,
+            );
+            assert_eq!(
+                workspace_proof.quotient_commitment,
+                proof.quotient_commitment
+            );
+
+            let wrong_output = Proof {
+                output_point: proof.output_point + Scalar::one(),
+                ..proof
+            };
+            assert!(!wrong_output.verify(input_point, opening_key));
+        }
+    }
 
     #[test]
     fn valid_proof_smoke() {
@@ -78,4 +151,71 @@ mod tests {
         assert!(proof.verify(input_point, &public_parameters.opening_key));
         assert!(!proof.verify(input_point + input_point, &public_parameters.opening_key));
     }
+
+    #[test]
+    fn workspace_proofs_match() {
+        let size = 2usize.pow(8);
+        let (public_parameters, domain) = test_setup(size);
+        let commit_key = &public_parameters.commit_key;
+
+        // The same workspace is reused for every proof
+        let mut workspace = ProverWorkspace::default();
+        for seed in 0..3u64 {
+            let poly = random_polynomial(size);
+            let input_point = Scalar::from(123456u64 + seed);
+
+            let poly_comm = commit_key.commit(&poly);
+            assert_eq!(
+                commit_key.commit_with_workspace(&poly, &mut workspace),
+                poly_comm
+            );
+            assert_eq!(
+                poly.evaluate_with_workspace(input_point, &domain, &mut workspace),
+                poly.evaluate(input_point, &domain)
+            );
+
+            let proof = Proof::create(commit_key, &poly, poly_comm, input_point, &domain);
+            let workspace_proof = Proof::create_with_workspace(
+                commit_key,
+                &poly,
+                poly_comm,
+                input_point,
+                &domain,
+                &mut workspace,
+            );
+            assert_eq!(
+                workspace_proof.quotient_commitment,
+                proof.quotient_commitment
+            );
+            assert_eq!(workspace_proof.output_point, proof.output_point);
+            assert!(workspace_proof.verify(input_point, &public_parameters.opening_key));
+        }
+    }
+
+    #[test]
+    fn open_inside_the_domain() {
+        let size = 16;
+        let (public_parameters, domain) = test_setup(size);
+        let poly = random_polynomial(size);
+
+        open_at_every_domain_element(
+            &public_parameters.commit_key,
+            &public_parameters.opening_key,
+            &poly,
+            &domain,
+        );
+
+        // EIP-4844 stores the domain, the lagrange points and the evaluations in bit-reversed order
+        let bit_reversed_domain = domain.bit_reverse_roots();
+        let bit_reversed_commit_key =
+            CommitKeyLagrange::new(bit_reverse(&public_parameters.commit_key.inner));
+        let bit_reversed_poly = Polynomial::new(bit_reverse(&poly.evaluations));
+
+        open_at_every_domain_element(
+            &bit_reversed_commit_key,
+            &public_parameters.opening_key,
+            &bit_reversed_poly,
+            &bit_reversed_domain,
+        );
+    }
 }
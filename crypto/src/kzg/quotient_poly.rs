@@ -1,4 +1,7 @@
-use crate::{batch_inversion::batch_inverse, Domain, Polynomial, Scalar};
+use crate::{
+    batch_inversion::{batch_inverse, batch_inverse_with_scratch},
+    Domain, Polynomial, ProverWorkspace, Scalar,
+};
 use ff::Field;
 
 /// Computes the quotient polynomial for a kzg proof
@@ -12,87 +15,145 @@ pub(crate) fn compute(
     output_point: Scalar,
     domain: &Domain,
 ) -> Polynomial {
+    let mut quotient = Vec::with_capacity(domain.size());
+    compute_into(
+        poly,
+        input_point,
+        output_point,
+        domain,
+        &mut quotient,
+        batch_inverse,
+    );
+    Polynomial::new(quotient)
+}
+
+/// Computes the quotient polynomial for a kzg proof,
+/// storing its evaluations in `workspace.quotient` instead of allocating
+pub(crate) fn compute_with_workspace(
+    poly: &Polynomial,
+    input_point: Scalar,
+    output_point: Scalar,
+    domain: &Domain,
+    workspace: &mut ProverWorkspace,
+) {
+    compute_into(
+        poly,
+        input_point,
+        output_point,
+        domain,
+        &mut workspace.quotient,
+        |denominators| batch_inverse_with_scratch(denominators, &mut workspace.inversion),
+    )
+}
+
+// Overwrites `quotient` with the evaluations of the quotient polynomial.
+// `invert` is used to batch invert the denominators
+fn compute_into(
+    poly: &Polynomial,
+    input_point: Scalar,
+    output_point: Scalar,
+    domain: &Domain,
+    quotient: &mut Vec<Scalar>,
+    invert: impl FnOnce(&mut [Scalar]),
+) {
     match domain.find(&input_point) {
-        Some(index_in_domain) => {
-            compute_quotient_in_domain(poly, index_in_domain, output_point, domain)
-        }
-        None => compute_quotient_outside_domain(poly, input_point, output_point, domain),
+        Some(index_in_domain) => compute_quotient_in_domain(
+            poly,
+            index_in_domain,
+            output_point,
+            domain,
+            quotient,
+            invert,
+        ),
+        None => compute_quotient_outside_domain(
+            poly,
+            input_point,
+            output_point,
+            domain,
+            quotient,
+            invert,
+        ),
     }
 }
 
-pub(crate) fn compute_quotient_in_domain(
+fn compute_quotient_in_domain(
     poly: &Polynomial,
     index_in_domain: usize,
     output_point: Scalar,
     domain: &Domain,
-) -> Polynomial {
-    let polynomial_shifted: Vec<_> = poly
-        .evaluations
-        .iter()
-        .map(|evaluation| evaluation - output_point)
-        .collect();
-
+    quotient: &mut Vec<Scalar>,
+    invert: impl FnOnce(&mut [Scalar]),
+) {
     let input_point = domain[index_in_domain];
-    let mut denominator_poly: Vec<_> = domain
-        .roots()
-        .iter()
-        .map(|root| root - input_point)
-        .collect();
-    denominator_poly[index_in_domain] = Scalar::one();
-    batch_inverse(&mut denominator_poly);
 
-    let mut quotient_poly = vec![Scalar::zero(); domain.size()];
-    for i in 0..domain.size() {
-        if i == index_in_domain {
-            quotient_poly[i] =
-                compute_quotient_eval_within_domain(poly, index_in_domain, output_point, domain)
-        } else {
-            quotient_poly[i] = polynomial_shifted[i] * denominator_poly[i]
+    // Compute the denominator and store it in the quotient vector, to avoid re-allocation.
+    // The denominator is zero at `index_in_domain`, so we replace it with one
+    // to be able to batch invert, and compute that evaluation separately.
+    quotient.clear();
+    quotient.extend(domain.roots().iter().map(|root| root - input_point));
+    quotient[index_in_domain] = Scalar::one();
+    invert(quotient);
+
+    for (i, (quotient_i, eval_i)) in quotient.iter_mut().zip(&poly.evaluations).enumerate() {
+        if i != index_in_domain {
+            *quotient_i *= *eval_i - output_point
         }
     }
 
-    Polynomial::new(quotient_poly)
+    quotient[index_in_domain] =
+        compute_quotient_eval_within_domain(quotient, index_in_domain, domain);
 }
 
+// Computes the evaluation of the quotient at the input point,
+// which is the domain element at `index_in_domain`.
+//
+// The formula is:
+//
+// q(z) = \sum_{i != m} (f_i - y) * w_i / (z * (z - w_i))
+//
+// Where `z = w_m`. Every other evaluation of the quotient is q_i = (f_i - y) / (w_i - z),
+// so this is the same as:
+//
+// q(z) = -z^{-1} * \sum_{i != m} q_i * w_i
+//
+// This reuses the inverses that were already batch inverted for the other evaluations.
 fn compute_quotient_eval_within_domain(
-    poly: &Polynomial,
+    quotient: &[Scalar],
     index_in_domain: usize,
-    output_point: Scalar,
     domain: &Domain,
 ) -> Scalar {
-    // TODO Assumes that index_in_domain is in the domain
-    // TODO: should we use a special Index struct/enum to encode this?
     let input_point = domain[index_in_domain];
 
-    // TODO: optimize with batch_inverse
-    let mut result = Scalar::zero();
-    for (index, root) in domain.roots().iter().enumerate() {
-        if index == index_in_domain {
-            continue;
+    let mut sum = Scalar::zero();
+    for (index, (quotient_i, root)) in quotient.iter().zip(domain.roots()).enumerate() {
+        if index != index_in_domain {
+            sum += quotient_i * root;
         }
-
-        let f_i = poly[index] - output_point;
-        let numerator = f_i * root;
-        let denominator = input_point * (input_point - root);
-        result += numerator * denominator.invert().unwrap()
     }
 
-    todo!()
+    // Domain elements are roots of unity, so they are never zero
+    let input_point_inv = input_point.invert().unwrap();
+    -(sum * input_point_inv)
 }
-pub(crate) fn compute_quotient_outside_domain(
+
+fn compute_quotient_outside_domain(
     poly: &Polynomial,
     input_point: Scalar,
     output_point: Scalar,
     domain: &Domain,
-) -> Polynomial {
+    quotient: &mut Vec<Scalar>,
+    invert: impl FnOnce(&mut [Scalar]),
+) {
     // Compute the denominator and store it in the quotient vector, to avoid re-allocation
-    let mut quotient: Vec<_> = domain
-        .roots()
-        .iter()
-        .map(|domain_element| *domain_element - input_point)
-        .collect();
+    quotient.clear();
+    quotient.extend(
+        domain
+            .roots()
+            .iter()
+            .map(|domain_element| *domain_element - input_point),
+    );
     // This should not panic, since we assume `input_point` is not in the domain
-    batch_inverse(&mut quotient);
+    invert(quotient);
 
     // Compute the numerator polynomial and multiply it by the quotient which holds the
     // denominator
@@ -109,6 +170,4 @@ pub(crate) fn compute_quotient_outside_domain(
     //     quotient[i] = (poly.evaluations[i] - output) * denominator
     // }
     // quotient
-
-    Polynomial::new(quotient)
 }
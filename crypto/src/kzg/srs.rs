@@ -26,12 +26,12 @@ impl PublicParameters {
 
         let powers_of_tau_g1: Vec<G1Point> = (0..domain.size())
             .map(|index| {
-                let secret_exp = tau_fr.pow_vartime(&[index as u64]);
+                let secret_exp = tau_fr.pow_vartime([index as u64]);
                 (G1Point::generator() * secret_exp).into()
             })
             .collect();
 
-        let ck_lagrange = CommitKey::new(powers_of_tau_g1).into_lagrange(&domain);
+        let ck_lagrange = CommitKey::new(powers_of_tau_g1).into_lagrange(domain);
 
         PublicParameters {
             commit_key: ck_lagrange,
@@ -1,10 +1,15 @@
-#[cfg(test)]
 pub mod test_utils;
 
+#[macro_use]
+mod ops;
+
 mod batch_inversion;
 mod domain;
+mod fiat_shamir;
 mod kzg;
+mod poly_coeff;
 mod polynomial;
+mod workspace;
 
 pub type G1Point = blstrs::G1Affine;
 pub type G2Point = blstrs::G2Affine;
@@ -21,9 +26,15 @@ pub const G2_POINT_SERIALIZED_SIZE: usize = 96;
 // TODO: we can just make this the default type
 pub(crate) type G1Projective = blstrs::G1Projective;
 
-pub use domain::Domain;
+pub use domain::{Domain, ZeroCosetGenerator};
+pub use fiat_shamir::{reduce_bytes_to_scalar_be, Transcript};
 pub use kzg::{
+    aggregated_kzg::AggregatedKZG,
+    commit_key::{CommitKey, CommitKeyLagrange},
+    opening_key::OpeningKey,
     proof::{KZGWitness, Proof},
     srs::PublicParameters,
 };
-pub use polynomial::Polynomial;
+pub use poly_coeff::PolyCoeff;
+pub use polynomial::{InvalidDomainSize, Polynomial};
+pub use workspace::ProverWorkspace;
@@ -0,0 +1,15 @@
+// The operators on owned values use the ones on references,
+// so that each operation is only implemented once
+macro_rules! forward_owned_ops {
+    ($type:ty; $($trait:ident, $method:ident;)*) => {
+        $(
+            impl std::ops::$trait for $type {
+                type Output = $type;
+
+                fn $method(self, other: $type) -> $type {
+                    (&self).$method(&other)
+                }
+            }
+        )*
+    };
+}
@@ -0,0 +1,342 @@
+use crate::{Domain, Polynomial, Scalar};
+use ff::Field;
+use std::ops::{Add, Mul, Neg, Sub};
+
+// Below this many coefficients, schoolbook multiplication is faster than going through the FFT
+const FFT_MUL_THRESHOLD: usize = 64;
+
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+// Polynomial representation in coefficient form, starting with the constant term.
+//
+// Trailing zero coefficients are removed, so two polynomials are equal
+// exactly when their coefficients are equal. The zero polynomial has no coefficients.
+pub struct PolyCoeff {
+    pub(crate) coefficients: Vec<Scalar>,
+}
+
+impl PolyCoeff {
+    /// Creates a polynomial from its coefficients, starting with the constant term
+    pub fn new(coefficients: Vec<Scalar>) -> PolyCoeff {
+        let mut coefficients = coefficients;
+        remove_trailing_zeros(&mut coefficients);
+        PolyCoeff { coefficients }
+    }
+
+    /// Returns the zero polynomial
+    pub fn zero() -> PolyCoeff {
+        PolyCoeff::default()
+    }
+
+    /// Interpolates the polynomial with the given evaluations over the domain
+    pub fn from_polynomial(polynomial: &Polynomial, domain: &Domain) -> PolyCoeff {
+        PolyCoeff::new(domain.ifft_scalars(polynomial.evaluations.clone()))
+    }
+
+    /// Evaluates the polynomial over the domain.
+    ///
+    /// Panics, if the polynomial has more coefficients than the size of the domain
+    pub fn to_polynomial(&self, domain: &Domain) -> Polynomial {
+        Polynomial::new(domain.fft_scalars(self.coefficients.clone()))
+    }
+
+    /// Returns the coefficients, starting with the constant term
+    pub fn coefficients(&self) -> &[Scalar] {
+        &self.coefficients
+    }
+
+    /// Returns the coefficients, so that their buffer can be reused
+    pub fn into_coefficients(self) -> Vec<Scalar> {
+        self.coefficients
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.coefficients.is_empty()
+    }
+
+    /// Returns the degree of the polynomial.
+    ///
+    /// The zero polynomial is treated as having degree 0
+    pub fn degree(&self) -> usize {
+        self.coefficients.len().saturating_sub(1)
+    }
+
+    /// Evaluates the polynomial at `z` using Horner's method
+    pub fn evaluate(&self, z: Scalar) -> Scalar {
+        self.coefficients
+            .iter()
+            .rev()
+            .fold(Scalar::zero(), |result, coefficient| {
+                result * z + coefficient
+            })
+    }
+
+    /// Multiplies the polynomials by multiplying every pair of coefficients
+    pub fn mul_naive(&self, other: &PolyCoeff) -> PolyCoeff {
+        if self.is_zero() || other.is_zero() {
+            return PolyCoeff::zero();
+        }
+
+        let mut product =
+            vec![Scalar::zero(); self.coefficients.len() + other.coefficients.len() - 1];
+        for (i, a) in self.coefficients.iter().enumerate() {
+            for (j, b) in other.coefficients.iter().enumerate() {
+                product[i + j] += a * b;
+            }
+        }
+        PolyCoeff::new(product)
+    }
+
+    /// Multiplies the polynomials by multiplying their evaluations over a domain
+    /// which is large enough to hold the product
+    pub fn mul_fft(&self, other: &PolyCoeff) -> PolyCoeff {
+        if self.is_zero() || other.is_zero() {
+            return PolyCoeff::zero();
+        }
+
+        let domain = Domain::new(self.coefficients.len() + other.coefficients.len() - 1);
+        let mut product = domain.fft_scalars(self.coefficients.clone());
+        let other_evaluations = domain.fft_scalars(other.coefficients.clone());
+        for (a, b) in product.iter_mut().zip(other_evaluations) {
+            *a *= b;
+        }
+        PolyCoeff::new(domain.ifft_scalars(product))
+    }
+
+    /// Divides the polynomial by `divisor`, and returns the quotient and the remainder.
+    ///
+    /// Panics, if `divisor` is the zero polynomial
+    pub fn div_rem(&self, divisor: &PolyCoeff) -> (PolyCoeff, PolyCoeff) {
+        assert!(!divisor.is_zero(), "cannot divide by the zero polynomial");
+
+        let mut remainder = self.coefficients.clone();
+        if remainder.len() < divisor.coefficients.len() {
+            return (PolyCoeff::zero(), self.clone());
+        }
+
+        let divisor_degree = divisor.degree();
+        let leading_coefficient_inv = divisor.coefficients[divisor_degree].invert().unwrap();
+
+        // Each step cancels the highest remaining coefficient of the remainder
+        let mut quotient = vec![Scalar::zero(); remainder.len() - divisor_degree];
+        for i in (0..quotient.len()).rev() {
+            let factor = remainder[i + divisor_degree] * leading_coefficient_inv;
+            quotient[i] = factor;
+            for (remainder_j, divisor_j) in remainder[i..].iter_mut().zip(&divisor.coefficients) {
+                *remainder_j -= factor * divisor_j;
+            }
+        }
+        remainder.truncate(divisor_degree);
+
+        (PolyCoeff::new(quotient), PolyCoeff::new(remainder))
+    }
+
+    /// Divides the polynomial by `X - z`, and returns the quotient and the remainder.
+    ///
+    /// The remainder is the evaluation of the polynomial at `z`
+    pub fn divide_by_linear(&self, z: Scalar) -> (PolyCoeff, Scalar) {
+        let Some((&leading_coefficient, rest)) = self.coefficients.split_last() else {
+            return (PolyCoeff::zero(), Scalar::zero());
+        };
+
+        // Synthetic division, starting from the highest coefficient
+        let mut quotient = vec![Scalar::zero(); rest.len()];
+        let mut carry = leading_coefficient;
+        for (quotient_i, coefficient) in quotient.iter_mut().zip(rest).rev() {
+            *quotient_i = carry;
+            carry = carry * z + coefficient;
+        }
+
+        (PolyCoeff::new(quotient), carry)
+    }
+}
+
+fn remove_trailing_zeros(coefficients: &mut Vec<Scalar>) {
+    while coefficients.last() == Some(&Scalar::zero()) {
+        coefficients.pop();
+    }
+}
+
+impl Add for &PolyCoeff {
+    type Output = PolyCoeff;
+
+    fn add(self, other: &PolyCoeff) -> PolyCoeff {
+        let (longer, shorter) = if self.coefficients.len() >= other.coefficients.len() {
+            (self, other)
+        } else {
+            (other, self)
+        };
+
+        let mut sum = longer.coefficients.clone();
+        for (a, b) in sum.iter_mut().zip(&shorter.coefficients) {
+            *a += b;
+        }
+        PolyCoeff::new(sum)
+    }
+}
+
+impl Sub for &PolyCoeff {
+    type Output = PolyCoeff;
+
+    fn sub(self, other: &PolyCoeff) -> PolyCoeff {
+        self + &(-other)
+    }
+}
+
+impl Neg for &PolyCoeff {
+    type Output = PolyCoeff;
+
+    fn neg(self) -> PolyCoeff {
+        PolyCoeff {
+            coefficients: self.coefficients.iter().map(|c| -c).collect(),
+        }
+    }
+}
+
+impl Mul for &PolyCoeff {
+    type Output = PolyCoeff;
+
+    fn mul(self, other: &PolyCoeff) -> PolyCoeff {
+        if self.coefficients.len().min(other.coefficients.len()) < FFT_MUL_THRESHOLD {
+            self.mul_naive(other)
+        } else {
+            self.mul_fft(other)
+        }
+    }
+}
+
+impl Mul<Scalar> for &PolyCoeff {
+    type Output = PolyCoeff;
+
+    fn mul(self, scalar: Scalar) -> PolyCoeff {
+        PolyCoeff::new(self.coefficients.iter().map(|c| c * scalar).collect())
+    }
+}
+
+forward_owned_ops! {
+    PolyCoeff;
+    Add, add;
+    Sub, sub;
+    Mul, mul;
+}
+
+impl Neg for PolyCoeff {
+    type Output = PolyCoeff;
+
+    fn neg(self) -> PolyCoeff {
+        -&self
+    }
+}
+
+impl Mul<Scalar> for PolyCoeff {
+    type Output = PolyCoeff;
+
+    fn mul(self, scalar: Scalar) -> PolyCoeff {
+        &self * scalar
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::random_vector;
+
+    fn random_poly(num_coefficients: usize) -> PolyCoeff {
+        PolyCoeff::new(random_vector(num_coefficients))
+    }
+
+    #[test]
+    fn evaluate_matches_naive_evaluation() {
+        let poly = random_poly(10);
+        let z = Scalar::from(1234u64);
+
+        let mut expected = Scalar::zero();
+        for (index, coefficient) in poly.coefficients().iter().enumerate() {
+            expected += z.pow_vartime([index as u64]) * coefficient;
+        }
+        assert_eq!(poly.evaluate(z), expected);
+        assert_eq!(PolyCoeff::zero().evaluate(z), Scalar::zero());
+    }
+
+    #[test]
+    fn trailing_zeros_are_removed() {
+        let poly = PolyCoeff::new(vec![Scalar::one(), Scalar::zero(), Scalar::zero()]);
+        assert_eq!(poly.coefficients(), &[Scalar::one()]);
+        assert_eq!(poly.degree(), 0);
+
+        let poly = random_poly(5);
+        assert!((&poly - &poly).is_zero());
+        assert!((&poly * Scalar::zero()).is_zero());
+    }
+
+    #[test]
+    fn arithmetic_matches_evaluations() {
+        let z = Scalar::from(98765u64);
+        let scalar = Scalar::from(3u64);
+        for (len_a, len_b) in [(0, 3), (3, 7), (7, 3), (70, 100)] {
+            let a = random_poly(len_a);
+            let b = random_poly(len_b);
+            let (a_z, b_z) = (a.evaluate(z), b.evaluate(z));
+
+            assert_eq!((&a + &b).evaluate(z), a_z + b_z);
+            assert_eq!((&a - &b).evaluate(z), a_z - b_z);
+            assert_eq!((-&a).evaluate(z), -a_z);
+            assert_eq!((&a * scalar).evaluate(z), a_z * scalar);
+
+            let product = a.mul_naive(&b);
+            assert_eq!(product.evaluate(z), a_z * b_z);
+            assert_eq!(a.mul_fft(&b), product);
+            assert_eq!(a.clone() * b.clone(), product);
+        }
+    }
+
+    #[test]
+    fn div_rem_reconstructs_the_dividend() {
+        for (len_a, len_b) in [(10, 4), (4, 10), (10, 1), (10, 10)] {
+            let a = random_poly(len_a);
+            let b = random_poly(len_b);
+
+            let (quotient, remainder) = a.div_rem(&b);
+            assert!(remainder.is_zero() || remainder.degree() < b.degree());
+            assert_eq!(&(&quotient * &b) + &remainder, a);
+        }
+
+        // Dividing a product leaves no remainder
+        let a = random_poly(8);
+        let b = random_poly(5);
+        assert_eq!((&a * &b).div_rem(&b), (a, PolyCoeff::zero()));
+    }
+
+    #[test]
+    fn divide_by_linear_matches_div_rem() {
+        let poly = random_poly(16);
+        let z = Scalar::from(42u64);
+        let linear = PolyCoeff::new(vec![-z, Scalar::one()]);
+
+        let (quotient, remainder) = poly.divide_by_linear(z);
+        assert_eq!(remainder, poly.evaluate(z));
+        assert_eq!(
+            poly.div_rem(&linear),
+            (quotient, PolyCoeff::new(vec![remainder]))
+        );
+        assert_eq!(
+            PolyCoeff::zero().divide_by_linear(z),
+            (PolyCoeff::zero(), Scalar::zero())
+        );
+    }
+
+    #[test]
+    fn polynomial_conversions_round_trip() {
+        let natural = Domain::new(16);
+        let bit_reversed = natural.clone().bit_reverse_roots();
+
+        for domain in [natural, bit_reversed] {
+            let poly = random_poly(12);
+
+            let polynomial = poly.to_polynomial(&domain);
+            for (root, evaluation) in domain.roots.iter().zip(&polynomial.evaluations) {
+                assert_eq!(poly.evaluate(*root), *evaluation);
+            }
+            assert_eq!(PolyCoeff::from_polynomial(&polynomial, &domain), poly);
+        }
+    }
+}
@@ -1,5 +1,9 @@
-use crate::{batch_inversion::batch_inverse, Domain, Scalar};
+use crate::{
+    batch_inversion::{batch_inverse, batch_inverse_with_scratch},
+    Domain, ProverWorkspace, Scalar,
+};
 use group::ff::Field;
+use std::ops::{Add, Mul, Sub};
 
 #[derive(Debug, Clone)]
 // Polynomial representation in evaluation form
@@ -8,6 +12,24 @@ pub struct Polynomial {
     pub(crate) evaluations: Vec<Scalar>,
 }
 
+/// The number of evaluations given to `Polynomial::try_new` was not a power of two
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InvalidDomainSize {
+    pub size: usize,
+}
+
+impl std::fmt::Display for InvalidDomainSize {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "the domain size must be a power of two, size is : {}",
+            self.size
+        )
+    }
+}
+
+impl std::error::Error for InvalidDomainSize {}
+
 impl PartialEq for Polynomial {
     fn eq(&self, other: &Self) -> bool {
         self.evaluations == other.evaluations
@@ -23,25 +45,55 @@ impl std::ops::Index<usize> for &Polynomial {
 }
 
 impl Polynomial {
-    /// Panics, if the number of evaluations is 0 or not a power of two
-    /// 0 is not a power of two, so we can remove it
+    /// Panics, if the number of evaluations is 0 or not a power of two.
+    ///
+    /// Use `Polynomial::try_new` if the number of evaluations has not been checked.
     pub fn new(evaluations: Vec<Scalar>) -> Polynomial {
-        // We could return an Option, users of the library
-        // who consume this API directly, will be forced to unwrap.
-        //
-        // We may change it in the future, as the further up the stack
-        // we put the unwrap, the more visible this is.
-
-        assert!(
-            evaluations.len().is_power_of_two(),
-            "the domain size must be a power of two, size is : {}",
-            evaluations.len()
-        );
+        match Polynomial::try_new(evaluations) {
+            Ok(polynomial) => polynomial,
+            Err(InvalidDomainSize { size }) => {
+                panic!("the domain size must be a power of two, size is : {size}")
+            }
+        }
+    }
 
-        Polynomial { evaluations }
+    /// Returns an error, if the number of evaluations is 0 or not a power of two
+    pub fn try_new(evaluations: Vec<Scalar>) -> Result<Polynomial, InvalidDomainSize> {
+        // 0 is not a power of two, so it does not need its own check
+        if !evaluations.len().is_power_of_two() {
+            return Err(InvalidDomainSize {
+                size: evaluations.len(),
+            });
+        }
+
+        Ok(Polynomial { evaluations })
     }
 
     pub fn evaluate(&self, z: Scalar, domain: &Domain) -> Scalar {
+        self.evaluate_with(z, domain, &mut Vec::new(), batch_inverse)
+    }
+
+    /// Evaluates the polynomial at `z`, using `workspace` instead of allocating
+    pub fn evaluate_with_workspace(
+        &self,
+        z: Scalar,
+        domain: &Domain,
+        workspace: &mut ProverWorkspace,
+    ) -> Scalar {
+        // The quotient has not been computed yet, so its buffer is free to hold the denominators
+        self.evaluate_with(z, domain, &mut workspace.quotient, |denominators| {
+            batch_inverse_with_scratch(denominators, &mut workspace.inversion)
+        })
+    }
+
+    // `denominators` is used as scratch space, and `invert` is used to batch invert it
+    fn evaluate_with(
+        &self,
+        z: Scalar,
+        domain: &Domain,
+        denominators: &mut Vec<Scalar>,
+        invert: impl FnOnce(&mut [Scalar]),
+    ) -> Scalar {
         assert_eq!(
             self.num_evaluations(),
             domain.size(),
@@ -50,27 +102,204 @@ impl Polynomial {
 
         match domain.find(&z) {
             Some(index_in_domain) => self.evaluations[index_in_domain],
-            None => self.evaluate_outside_of_domain(z, domain),
+            None => self.evaluate_outside_of_domain(z, domain, denominators, invert),
         }
     }
 
     // Using the barycentric formula, one can evaluate a polynomial
     // in evaluation form, on a point `z` that is not inside of its domain
-    fn evaluate_outside_of_domain(&self, z: Scalar, domain: &Domain) -> Scalar {
+    fn evaluate_outside_of_domain(
+        &self,
+        z: Scalar,
+        domain: &Domain,
+        denominator: &mut Vec<Scalar>,
+        invert: impl FnOnce(&mut [Scalar]),
+    ) -> Scalar {
         let domain_size = domain.size();
 
-        let mut denominator: Vec<_> = domain.roots().iter().map(|root_i| z - root_i).collect();
-        batch_inverse(&mut denominator);
+        denominator.clear();
+        denominator.extend(domain.roots().iter().map(|root_i| z - root_i));
+        invert(denominator);
 
         let mut result = Scalar::zero();
         // TODO Use zip here on evals, domain and denominator
         for i in 0..domain_size {
             result += (self.evaluations[i] * domain[i]) * denominator[i];
         }
-        result * (z.pow_vartime(&[domain_size as u64]) - Scalar::one()) * domain.domain_size_inv
+        result * (z.pow_vartime([domain_size as u64]) - Scalar::one()) * domain.domain_size_inv
+    }
+
+    /// Returns the evaluations of the polynomial, in the order of the domain
+    pub fn evaluations(&self) -> &[Scalar] {
+        &self.evaluations
+    }
+
+    /// Returns the evaluations, so that their buffer can be reused
+    pub fn into_evaluations(self) -> Vec<Scalar> {
+        self.evaluations
+    }
+
+    /// Computes the sum of the polynomials, each multiplied by its coefficient.
+    ///
+    /// Panics, if there are no polynomials, if the number of polynomials and coefficients
+    /// differ, or if the polynomials are over domains of different sizes
+    pub fn linear_combination(polys: &[Polynomial], coefficients: &[Scalar]) -> Polynomial {
+        assert_eq!(
+            polys.len(),
+            coefficients.len(),
+            "the number of polynomials != the number of coefficients"
+        );
+        assert!(!polys.is_empty(), "cannot combine zero polynomials");
+
+        let mut result = vec![Scalar::zero(); polys[0].num_evaluations()];
+        for (poly, coefficient) in polys.iter().zip(coefficients) {
+            polys[0].assert_same_domain_size(poly);
+            for (result_i, evaluation_i) in result.iter_mut().zip(&poly.evaluations) {
+                *result_i += evaluation_i * coefficient;
+            }
+        }
+
+        Polynomial::new(result)
+    }
+
+    // Combines the evaluations of the two polynomials at each point of the domain
+    fn pointwise(&self, other: &Polynomial, op: impl Fn(&Scalar, &Scalar) -> Scalar) -> Polynomial {
+        self.assert_same_domain_size(other);
+        let evaluations = self
+            .evaluations
+            .iter()
+            .zip(&other.evaluations)
+            .map(|(a, b)| op(a, b))
+            .collect();
+        Polynomial { evaluations }
+    }
+
+    fn assert_same_domain_size(&self, other: &Polynomial) {
+        assert_eq!(
+            self.num_evaluations(),
+            other.num_evaluations(),
+            "the polynomials are over domains of different sizes"
+        );
     }
 
     fn num_evaluations(&self) -> usize {
         self.evaluations.len()
     }
 }
+
+// The arithmetic is done pointwise on the evaluations,
+// and panics if the polynomials are over domains of different sizes.
+//
+// The product of two polynomials is only correct if its degree is less than the domain size.
+
+impl Add for &Polynomial {
+    type Output = Polynomial;
+
+    fn add(self, other: &Polynomial) -> Polynomial {
+        self.pointwise(other, |a, b| a + b)
+    }
+}
+
+impl Sub for &Polynomial {
+    type Output = Polynomial;
+
+    fn sub(self, other: &Polynomial) -> Polynomial {
+        self.pointwise(other, |a, b| a - b)
+    }
+}
+
+impl Mul for &Polynomial {
+    type Output = Polynomial;
+
+    fn mul(self, other: &Polynomial) -> Polynomial {
+        self.pointwise(other, |a, b| a * b)
+    }
+}
+
+impl Mul<Scalar> for &Polynomial {
+    type Output = Polynomial;
+
+    fn mul(self, scalar: Scalar) -> Polynomial {
+        let evaluations = self.evaluations.iter().map(|a| a * scalar).collect();
+        Polynomial { evaluations }
+    }
+}
+
+forward_owned_ops! {
+    Polynomial;
+    Add, add;
+    Sub, sub;
+    Mul, mul;
+}
+
+impl Mul<Scalar> for Polynomial {
+    type Output = Polynomial;
+
+    fn mul(self, scalar: Scalar) -> Polynomial {
+        &self * scalar
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::random_polynomial;
+
+    #[test]
+    fn arithmetic_matches_evaluations() {
+        let domain = Domain::new(16);
+        let a = random_polynomial(16);
+        let b = random_polynomial(16);
+        let scalar = Scalar::from(5u64);
+        // A point outside of the domain, so the barycentric formula is used
+        let z = Scalar::from(123456u64);
+        let (a_z, b_z) = (a.evaluate(z, &domain), b.evaluate(z, &domain));
+
+        assert_eq!((&a + &b).evaluate(z, &domain), a_z + b_z);
+        assert_eq!((&a - &b).evaluate(z, &domain), a_z - b_z);
+        assert_eq!((&a * scalar).evaluate(z, &domain), a_z * scalar);
+        assert_eq!(a.clone() * scalar, &a * scalar);
+        assert_eq!(a.clone() + b.clone(), &a + &b);
+
+        for i in 0..16 {
+            assert_eq!((&a * &b).evaluations()[i], (&a)[i] * (&b)[i]);
+        }
+    }
+
+    #[test]
+    fn linear_combination_matches_operators() {
+        let polys: Vec<_> = (0..3).map(|_| random_polynomial(8)).collect();
+        let coefficients: Vec<_> = (1..=3u64).map(Scalar::from).collect();
+
+        let expected = &(&(&polys[0] * coefficients[0]) + &(&polys[1] * coefficients[1]))
+            + &(&polys[2] * coefficients[2]);
+        assert_eq!(
+            Polynomial::linear_combination(&polys, &coefficients),
+            expected
+        );
+    }
+
+    #[test]
+    fn try_new_rejects_invalid_domain_sizes() {
+        for size in [0, 3, 12] {
+            assert_eq!(
+                Polynomial::try_new(vec![Scalar::one(); size]),
+                Err(InvalidDomainSize { size })
+            );
+        }
+        assert!(Polynomial::try_new(vec![Scalar::one(); 8]).is_ok());
+    }
+
+    #[test]
+    fn mismatched_domain_sizes_are_rejected() {
+        let a = random_polynomial(8);
+        let b = random_polynomial(16);
+
+        assert!(std::panic::catch_unwind(|| &a + &b).is_err());
+        assert!(std::panic::catch_unwind(|| &a * &b).is_err());
+        assert!(std::panic::catch_unwind(|| {
+            Polynomial::linear_combination(&[a.clone(), b.clone()], &[Scalar::one(); 2])
+        })
+        .is_err());
+    }
+}
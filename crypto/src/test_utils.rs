@@ -19,7 +19,7 @@ pub fn random_g1() -> G1Point {
     G1Point::generator().mul(rand_scalar).into()
 }
 
-pub(crate) fn test_setup(size: usize) -> (PublicParameters, Domain) {
+pub fn test_setup(size: usize) -> (PublicParameters, Domain) {
     let domain = Domain::new(size);
     let public_parameters = PublicParameters::from_secret_insecure(123456789, &domain);
     (public_parameters, domain)
@@ -0,0 +1,66 @@
+use crate::Scalar;
+
+/// Scratch space for creating commitments and proofs.
+///
+/// Creating a proof needs several buffers with one element per point in the domain.
+/// The `_with_workspace` methods store these in the workspace instead of allocating them,
+/// so once a workspace has been used for a domain size, using it again does not allocate.
+///
+/// A workspace can only be used by one thread at a time, so callers that prove
+/// in parallel should keep one workspace per thread.
+/// The methods that use it are single threaded.
+#[derive(Debug, Clone, Default)]
+pub struct ProverWorkspace {
+    // Holds the quotient polynomial, and the denominators used to compute it
+    pub(crate) quotient: Vec<Scalar>,
+    // The partial products used by batch inversion
+    pub(crate) inversion: Vec<Scalar>,
+    pub(crate) msm: MsmScratch,
+}
+
+impl ProverWorkspace {
+    /// Creates a workspace with enough space to prove over a domain of `domain_size` elements,
+    /// so that even the first use does not allocate
+    pub fn new(domain_size: usize) -> ProverWorkspace {
+        ProverWorkspace {
+            quotient: Vec::with_capacity(domain_size),
+            inversion: Vec::with_capacity(domain_size),
+            msm: MsmScratch::new(domain_size),
+        }
+    }
+}
+
+// Scratch space for a multi-scalar multiplication using blst's pippenger implementation
+#[derive(Debug, Clone, Default)]
+pub(crate) struct MsmScratch {
+    // The scalars, serialized as little-endian bytes
+    pub(crate) scalar_bytes: Vec<u8>,
+    // The buckets used by pippenger
+    pub(crate) buckets: Vec<blst::limb_t>,
+}
+
+impl MsmScratch {
+    fn new(num_points: usize) -> MsmScratch {
+        let mut scratch = MsmScratch {
+            scalar_bytes: Vec::with_capacity(num_points * 32),
+            buckets: Vec::new(),
+        };
+        scratch.reserve_buckets(num_points);
+        scratch
+    }
+
+    // Makes sure that there are enough buckets for `num_points` points
+    pub(crate) fn reserve_buckets(&mut self, num_points: usize) {
+        if num_points == 0 {
+            return;
+        }
+        // SAFETY: this only computes a size, and is valid for any non-zero number of points.
+        // The size is in bytes, and `g1_lincomb_with_scratch` relies on `buckets`
+        // being at least that large
+        let size_in_bytes = unsafe { blst::blst_p1s_mult_pippenger_scratch_sizeof(num_points) };
+        let num_limbs = size_in_bytes.div_ceil(std::mem::size_of::<blst::limb_t>());
+        if self.buckets.len() < num_limbs {
+            self.buckets.resize(num_limbs, 0);
+        }
+    }
+}
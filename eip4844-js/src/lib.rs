@@ -1,5 +1,4 @@
 use js_sys::{Array, Uint8Array};
-use serde::{Deserialize, Serialize};
 use wasm_bindgen::{prelude::wasm_bindgen, JsValue};
 mod utils;
 
@@ -11,14 +10,22 @@ pub enum ContextError {
     FailedToCommit,
 }
 
-impl From<ContextError> for JsValue {
-    fn from(value: ContextError) -> Self {
-        match value {
+// `#[wasm_bindgen]` already converts the enum into its numeric value,
+// so the error message is thrown through this instead
+impl ContextError {
+    fn into_js_error(self) -> JsValue {
+        match self {
             ContextError::FailedToCommit => JsValue::from_str("failed to commit"),
         }
     }
 }
 
+impl Default for Context {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 #[wasm_bindgen]
 impl Context {
     #[wasm_bindgen(constructor)]
@@ -28,15 +35,15 @@ impl Context {
     }
 
     pub fn blob_to_kzg_commitment(&self, blob_bytes: Uint8Array) -> Option<Uint8Array> {
-        let blob_bytes = blob_bytes.to_vec();
-        let comm_bytes = self.0.blob_to_kzg_commitment(blob_bytes)?;
+        let blob = js_bytes_to_rust_blob(blob_bytes)?;
+        let comm_bytes = self.0.blob_to_kzg_commitment(&blob).ok()?;
         Some(Uint8Array::from(comm_bytes.as_slice()))
     }
 
     pub fn compute_aggregated_kzg_proof(&self, blobs_bytes: Array) -> Option<Array> {
-        let blobs_bytes = js_blobs_to_rust_blobs(blobs_bytes);
+        let blobs = js_blobs_to_rust_blobs(blobs_bytes)?;
 
-        let (proof, comms) = self.0.compute_aggregated_kzg_proof(blobs_bytes)?;
+        let (proof, comms) = self.0.compute_aggregated_kzg_proof(&blobs).ok()?;
 
         Some(rust_aggregated_proof_data_to_js_aggregated_proof_data(
             proof, comms,
@@ -49,12 +56,13 @@ impl Context {
         blob_comms_bytes: Array,
         witness_comm_bytes: Uint8Array,
     ) -> Option<bool> {
-        let blobs_bytes = js_blobs_to_rust_blobs(blobs_bytes);
+        let blobs = js_blobs_to_rust_blobs(blobs_bytes)?;
         let blob_comms_bytes = js_commitments_to_rust_commitments(blob_comms_bytes)?;
         let witness_comm_bytes = js_bytes_to_rust_commitment(witness_comm_bytes)?;
 
         self.0
-            .verify_aggregated_kzg_proof(blobs_bytes, blob_comms_bytes, witness_comm_bytes)
+            .verify_aggregated_kzg_proof(&blobs, &blob_comms_bytes, witness_comm_bytes)
+            .ok()
     }
 
     pub fn verify_kzg_proof(
@@ -71,61 +79,46 @@ impl Context {
 
         self.0
             .verify_kzg_proof(commitment, input_point, claimed_value, proof)
+            .ok()
     }
 
     // TODO: This does not give a result in the generated typescript
     // lets call it so it returns a value to see what actually happens
     // -- From docs, it should throw, so we should catch this in JS
     /// @throws {Err}
-    pub fn throws_hello(value: u8) -> Result<u8, ContextError> {
-        if value % 2 == 0 {
+    pub fn throws_hello(value: u8) -> Result<u8, JsValue> {
+        if value.is_multiple_of(2) {
             Ok(value)
         } else {
-            Err(ContextError::FailedToCommit)
+            Err(ContextError::FailedToCommit.into_js_error())
         }
     }
 }
 
 // conversion methods to convert between javascript types and rust types
-fn js_blobs_to_rust_blobs(arr: Array) -> Vec<eip4844::BlobBytes> {
-    arr.entries()
-        .into_iter()
-        .map(|entry| {
-            let entry = entry.unwrap();
-            Uint8Array::from(entry).to_vec()
-        })
+fn js_blobs_to_rust_blobs(arr: Array) -> Option<Vec<eip4844::Blob>> {
+    arr.iter()
+        .map(|entry| js_bytes_to_rust_blob(Uint8Array::from(entry)))
         .collect()
 }
-fn rust_blobs_to_js_blobs(blob_bytes: Vec<eip4844::BlobBytes>) -> Array {
-    let mut arr = Array::new_with_length(blob_bytes.len() as u32);
-    for (index, blob) in blob_bytes.into_iter().enumerate() {
-        arr.set(index as u32, Uint8Array::from(blob.as_slice()).into())
-    }
-    arr
+fn js_bytes_to_rust_blob(bytes: Uint8Array) -> Option<eip4844::Blob> {
+    eip4844::Blob::from_bytes(bytes.to_vec()).ok()
 }
-fn js_commitments_to_rust_commitments(comms: Array) -> Option<Vec<eip4844::SerialisedPoint>> {
+fn js_commitments_to_rust_commitments(comms: Array) -> Option<Vec<eip4844::KZGCommitmentBytes>> {
     let mut commitments = Vec::with_capacity(comms.length() as usize);
 
-    for entry in comms.entries() {
-        let comm_js = entry.unwrap();
+    for comm_js in comms.iter() {
         let bytes = Uint8Array::from(comm_js);
         commitments.push(js_bytes_to_rust_commitment(bytes)?)
     }
 
     Some(commitments)
 }
-fn rust_commitments_to_js_commitments(comms: Vec<eip4844::SerialisedPoint>) -> Array {
-    let arr = Array::new_with_length(comms.len() as u32);
-    for (index, comm) in comms.into_iter().enumerate() {
-        arr.set(index as u32, Uint8Array::from(comm.as_slice()).into())
-    }
-    arr
-}
-fn js_bytes_to_rust_commitment(bytes: Uint8Array) -> Option<eip4844::SerialisedPoint> {
-    Uint8Array::from(bytes).to_vec().try_into().ok()
+fn js_bytes_to_rust_commitment(bytes: Uint8Array) -> Option<eip4844::KZGCommitmentBytes> {
+    bytes.to_vec().try_into().ok()
 }
-fn js_bytes_to_rust_scalar(bytes: Uint8Array) -> Option<eip4844::SerialisedScalar> {
-    Uint8Array::from(bytes).to_vec().try_into().ok()
+fn js_bytes_to_rust_scalar(bytes: Uint8Array) -> Option<eip4844::SerializedScalar> {
+    bytes.to_vec().try_into().ok()
 }
 
 fn rust_aggregated_proof_data_to_js_aggregated_proof_data(
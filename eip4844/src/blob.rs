@@ -0,0 +1,204 @@
+use crate::{
+    constants::FIELD_ELEMENT_SIZE, deserialize_scalar, BlobBytes, KzgError, Preset,
+    SerializedScalar,
+};
+use crypto::{Polynomial, Scalar};
+
+/// A blob that is known to have the correct length,
+/// and whose field elements are known to be canonical.
+///
+/// Since these checks are done when the blob is created,
+/// methods which take a `Blob` cannot fail because of it.
+///
+/// Field elements are encoded as 32 byte big-endian integers,
+/// as in the consensus specs.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Blob {
+    bytes: BlobBytes,
+}
+
+impl Blob {
+    /// Checks that `bytes` is exactly `BYTES_PER_BLOB` long and that
+    /// every field element in it is canonical.
+    pub fn from_bytes(bytes: BlobBytes) -> Result<Blob, KzgError> {
+        Blob::from_bytes_with_preset(bytes, Preset::MAINNET)
+    }
+
+    /// Like `Blob::from_bytes`, but the blob must have the size given by `preset`
+    pub fn from_bytes_with_preset(bytes: BlobBytes, preset: Preset) -> Result<Blob, KzgError> {
+        let expected = preset.bytes_per_blob();
+        if bytes.len() != expected {
+            return Err(KzgError::InvalidBlobLength {
+                expected,
+                got: bytes.len(),
+            });
+        }
+
+        for (index, chunk) in bytes.chunks_exact(FIELD_ELEMENT_SIZE).enumerate() {
+            if deserialize_field_element(chunk).is_none() {
+                return Err(KzgError::NonCanonicalFieldElement { index });
+            }
+        }
+
+        Ok(Blob { bytes })
+    }
+
+    /// Like `Blob::from_bytes`, but each field element in `bytes` is
+    /// encoded as a little-endian integer.
+    ///
+    /// As with `Blob::from_bytes`, the blob must have the mainnet size.
+    ///
+    /// This is not the encoding used by the consensus specs, it is only
+    /// here for callers that still hold blobs in the old format.
+    pub fn from_bytes_le(mut bytes: BlobBytes) -> Result<Blob, KzgError> {
+        reverse_field_elements(&mut bytes);
+        Blob::from_bytes(bytes)
+    }
+
+    /// Returns the blob with each field element encoded as a little-endian integer
+    pub fn to_bytes_le(&self) -> BlobBytes {
+        let mut bytes = self.bytes.clone();
+        reverse_field_elements(&mut bytes);
+        bytes
+    }
+
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    pub fn into_bytes(self) -> BlobBytes {
+        self.bytes
+    }
+
+    /// The number of field elements in the blob
+    pub fn num_field_elements(&self) -> usize {
+        self.bytes.len() / FIELD_ELEMENT_SIZE
+    }
+
+    pub(crate) fn to_polynomial(&self) -> Polynomial {
+        self.to_polynomial_in(Vec::with_capacity(self.num_field_elements()))
+    }
+
+    /// Like `to_polynomial`, but reuses the `evaluations` buffer.
+    /// Its previous contents are discarded.
+    pub(crate) fn to_polynomial_in(&self, mut evaluations: Vec<Scalar>) -> Polynomial {
+        evaluations.clear();
+        for chunk in self.bytes.chunks_exact(FIELD_ELEMENT_SIZE) {
+            let field_element = deserialize_field_element(chunk)
+                .expect("infallible: field elements were checked when the blob was created");
+            evaluations.push(field_element)
+        }
+
+        // The number of field elements comes from a `Preset`, so it is a power of two and this will not panic
+        Polynomial::new(evaluations)
+    }
+}
+
+impl TryFrom<BlobBytes> for Blob {
+    type Error = KzgError;
+
+    fn try_from(bytes: BlobBytes) -> Result<Self, Self::Error> {
+        Blob::from_bytes(bytes)
+    }
+}
+
+impl AsRef<[u8]> for Blob {
+    fn as_ref(&self) -> &[u8] {
+        self.as_bytes()
+    }
+}
+
+// Switches the endianness of every field element in `bytes`.
+// A trailing chunk that is not a whole field element is left alone,
+// so that the length check still reports the original length.
+fn reverse_field_elements(bytes: &mut [u8]) {
+    for chunk in bytes.chunks_exact_mut(FIELD_ELEMENT_SIZE) {
+        chunk.reverse();
+    }
+}
+
+fn deserialize_field_element(chunk: &[u8]) -> Option<Scalar> {
+    let chunk: &SerializedScalar = chunk
+        .try_into()
+        .expect("infallible: chunks are FIELD_ELEMENT_SIZE bytes");
+    deserialize_scalar(chunk)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constants::{BYTES_PER_BLOB, FIELD_ELEMENTS_PER_BLOB};
+
+    #[test]
+    fn blob_length_is_checked() {
+        for len in [
+            0,
+            FIELD_ELEMENT_SIZE,
+            BYTES_PER_BLOB - 1,
+            BYTES_PER_BLOB + 1,
+        ] {
+            assert_eq!(
+                Blob::from_bytes(vec![0; len]),
+                Err(KzgError::InvalidBlobLength {
+                    expected: BYTES_PER_BLOB,
+                    got: len
+                })
+            );
+        }
+        assert!(Blob::from_bytes(vec![0; BYTES_PER_BLOB]).is_ok());
+    }
+
+    #[test]
+    fn blob_field_elements_are_checked() {
+        let mut bytes = vec![0; BYTES_PER_BLOB];
+        let index = FIELD_ELEMENTS_PER_BLOB - 1;
+        bytes[index * FIELD_ELEMENT_SIZE..].fill(0xff);
+
+        assert_eq!(
+            Blob::from_bytes(bytes),
+            Err(KzgError::NonCanonicalFieldElement { index })
+        );
+    }
+
+    #[test]
+    fn little_endian_codec() {
+        let mut bytes = vec![0; BYTES_PER_BLOB];
+        // The first field element is 1, in big-endian
+        bytes[FIELD_ELEMENT_SIZE - 1] = 1;
+        let blob = Blob::from_bytes(bytes).unwrap();
+
+        let bytes_le = blob.to_bytes_le();
+        assert_eq!(bytes_le[0], 1);
+        assert_eq!(Blob::from_bytes_le(bytes_le).unwrap(), blob);
+
+        // Canonicity is checked after the bytes have been reversed
+        let mut bytes_le = vec![0; BYTES_PER_BLOB];
+        bytes_le[FIELD_ELEMENT_SIZE - 1] = 0xff;
+        assert_eq!(
+            Blob::from_bytes_le(bytes_le),
+            Err(KzgError::NonCanonicalFieldElement { index: 0 })
+        );
+    }
+
+    #[test]
+    fn blob_size_follows_the_preset() {
+        let minimal_bytes = Preset::MINIMAL.bytes_per_blob();
+        let blob = Blob::from_bytes_with_preset(vec![0; minimal_bytes], Preset::MINIMAL).unwrap();
+        assert_eq!(blob.num_field_elements(), 4);
+
+        assert_eq!(
+            Blob::from_bytes(vec![0; minimal_bytes]),
+            Err(KzgError::InvalidBlobLength {
+                expected: BYTES_PER_BLOB,
+                got: minimal_bytes
+            })
+        );
+        assert_eq!(
+            Blob::from_bytes_with_preset(vec![0; BYTES_PER_BLOB], Preset::MINIMAL),
+            Err(KzgError::InvalidBlobLength {
+                expected: minimal_bytes,
+                got: BYTES_PER_BLOB
+            })
+        );
+    }
+}
@@ -0,0 +1,323 @@
+//! A binary cache file for a fully initialized `Context`.
+//!
+//! Building a `Context` means decompressing and validating every point in the
+//! trusted setup, and permuting them. The cache stores the result, so that it can be
+//! loaded again without redoing that work.
+//!
+//! The layout is, with all integers in big-endian:
+//!
+//! - magic: 8 bytes, `CACHE_MAGIC`
+//! - version: u32, `CACHE_VERSION`
+//! - field elements per blob `n`: u64
+//! - the bit-reversed lagrange points: `n` uncompressed G1 points
+//! - the opening key: an uncompressed G1 point and two uncompressed G2 points
+//! - checksum: the SHA-256 hash of everything above
+//!
+//! The domain only depends on `n`, so it is recomputed rather than stored.
+use crate::{permutation::Permutable, Context, G1DecodingPolicy, KzgError, Preset};
+use crypto::{CommitKeyLagrange, Domain, G1Point, G2Point, OpeningKey, PublicParameters};
+use sha2::{Digest, Sha256};
+
+const CACHE_MAGIC: &[u8; 8] = b"KZGCTX\0\0";
+/// Bumped whenever the layout of the cache file changes
+const CACHE_VERSION: u32 = 2;
+
+const HEADER_SIZE: usize = CACHE_MAGIC.len() + 4 + 8;
+const CHECKSUM_SIZE: usize = 32;
+const G1_UNCOMPRESSED_SIZE: usize = 96;
+const G2_UNCOMPRESSED_SIZE: usize = 192;
+
+/// Errors that can occur when loading a `Context` from a cache file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CacheError {
+    /// The file does not start with the expected magic bytes
+    InvalidMagic,
+    /// The file was written by an incompatible version of this crate
+    UnsupportedVersion { expected: u32, got: u32 },
+    /// The file does not have the size implied by its header
+    InvalidLength { expected: usize, got: usize },
+    /// The contents could not be decoded, or the checksum did not match
+    /// and the points failed validation
+    InvalidContents,
+}
+
+impl std::fmt::Display for CacheError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CacheError::InvalidMagic => write!(f, "not a context cache file"),
+            CacheError::UnsupportedVersion { expected, got } => write!(
+                f,
+                "context cache has version {got}, expected version {expected}"
+            ),
+            CacheError::InvalidLength { expected, got } => {
+                write!(f, "context cache has {got} bytes, expected {expected}")
+            }
+            CacheError::InvalidContents => write!(f, "context cache contents are invalid"),
+        }
+    }
+}
+
+impl std::error::Error for CacheError {}
+
+impl Context {
+    /// Serializes the context, so that it can be loaded with `Context::from_cache_bytes`.
+    ///
+    /// The decoding policy is not stored, as it is not part of the setup.
+    pub fn to_cache_bytes(&self) -> Vec<u8> {
+        let n = self.preset.field_elements_per_blob();
+        let commit_key = &self.public_parameters.commit_key;
+        let opening_key = &self.public_parameters.opening_key;
+
+        let mut bytes = Vec::with_capacity(cache_size(n));
+        bytes.extend_from_slice(CACHE_MAGIC);
+        bytes.extend_from_slice(&CACHE_VERSION.to_be_bytes());
+        bytes.extend_from_slice(&(n as u64).to_be_bytes());
+
+        for point in &commit_key.inner {
+            bytes.extend_from_slice(&point.to_uncompressed());
+        }
+        bytes.extend_from_slice(&opening_key.g1_gen.to_uncompressed());
+        bytes.extend_from_slice(&opening_key.g2_gen.to_uncompressed());
+        bytes.extend_from_slice(&opening_key.tau_g2_gen.to_uncompressed());
+
+        let checksum = Sha256::digest(&bytes);
+        bytes.extend_from_slice(&checksum);
+
+        bytes
+    }
+
+    /// Loads a context that was serialized with `Context::to_cache_bytes`.
+    ///
+    /// If the checksum matches, the points are not checked to be in the prime order subgroup.
+    /// Otherwise, every point is fully validated, as it is when loading the trusted setup.
+    /// Even then, the cache should only be read from storage that is as trusted as the binary
+    /// itself, for example a file this node wrote, since the points are not compared
+    /// against the trusted setup.
+    pub fn from_cache_bytes(bytes: &[u8]) -> Result<Context, KzgError> {
+        Ok(Context::decode_cache(bytes)?)
+    }
+
+    fn decode_cache(bytes: &[u8]) -> Result<Context, CacheError> {
+        if bytes.len() < HEADER_SIZE || &bytes[..CACHE_MAGIC.len()] != CACHE_MAGIC {
+            return Err(CacheError::InvalidMagic);
+        }
+        let mut reader = Reader {
+            bytes: &bytes[CACHE_MAGIC.len()..],
+            validate: true,
+        };
+
+        let version = u32::from_be_bytes(reader.take());
+        if version != CACHE_VERSION {
+            return Err(CacheError::UnsupportedVersion {
+                expected: CACHE_VERSION,
+                got: version,
+            });
+        }
+
+        let n = u64::from_be_bytes(reader.take());
+        let preset = usize::try_from(n)
+            .ok()
+            .and_then(|n| Preset::new(n).ok())
+            .ok_or(CacheError::InvalidContents)?;
+        let n = preset.field_elements_per_blob();
+
+        let expected = cache_size(n);
+        if bytes.len() != expected {
+            return Err(CacheError::InvalidLength {
+                expected,
+                got: bytes.len(),
+            });
+        }
+
+        // The subgroup checks are only skipped when the checksum matches
+        let (contents, checksum) = bytes.split_at(expected - CHECKSUM_SIZE);
+        reader.validate = Sha256::digest(contents).as_slice() != checksum;
+
+        let g1_lagrange = (0..n)
+            .map(|_| reader.g1_point())
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let g1_gen = reader.g1_point()?;
+        let g2_gen = reader.g2_point()?;
+        let tau_g2_gen = reader.g2_point()?;
+
+        // This also computes the twiddle factors for the FFT, which are not stored
+        let domain = Domain::new(n).permute();
+        let public_parameters = PublicParameters {
+            commit_key: CommitKeyLagrange { inner: g1_lagrange },
+            opening_key: OpeningKey::new(g1_gen, g2_gen, tau_g2_gen),
+        };
+
+        Ok(Context {
+            public_parameters,
+            domain,
+            preset,
+            decoding_policy: G1DecodingPolicy::default(),
+        })
+    }
+}
+
+// The size is saturated rather than overflowing on 32 bit targets,
+// since `n` comes from the file and may be very large
+fn cache_size(n: usize) -> usize {
+    n.saturating_mul(G1_UNCOMPRESSED_SIZE)
+        .saturating_add(HEADER_SIZE)
+        .saturating_add(G1_UNCOMPRESSED_SIZE)
+        .saturating_add(2 * G2_UNCOMPRESSED_SIZE)
+        .saturating_add(CHECKSUM_SIZE)
+}
+
+// Reads fixed size items from the front of a byte slice.
+//
+// The length of the cache is checked before reading from it, so `take` does not fail.
+// Points are only checked to be in the prime order subgroup if `validate` is set.
+struct Reader<'a> {
+    bytes: &'a [u8],
+    validate: bool,
+}
+
+impl Reader<'_> {
+    fn take<const N: usize>(&mut self) -> [u8; N] {
+        let (head, tail) = self.bytes.split_at(N);
+        self.bytes = tail;
+        head.try_into()
+            .expect("infallible: split_at returns N bytes")
+    }
+
+    fn g1_point(&mut self) -> Result<G1Point, CacheError> {
+        let bytes = self.take();
+        let point = if self.validate {
+            G1Point::from_uncompressed(&bytes)
+        } else {
+            G1Point::from_uncompressed_unchecked(&bytes)
+        };
+        Option::from(point).ok_or(CacheError::InvalidContents)
+    }
+
+    fn g2_point(&mut self) -> Result<G2Point, CacheError> {
+        let bytes = self.take();
+        let point = if self.validate {
+            G2Point::from_uncompressed(&bytes)
+        } else {
+            G2Point::from_uncompressed_unchecked(&bytes)
+        };
+        Option::from(point).ok_or(CacheError::InvalidContents)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn load(bytes: &[u8]) -> Result<Context, CacheError> {
+        Context::decode_cache(bytes)
+    }
+
+    #[test]
+    fn cache_round_trip() {
+        let context = Context::new_insecure_with_preset(Preset::new(16).unwrap());
+        let bytes = context.to_cache_bytes();
+        assert_eq!(bytes.len(), cache_size(16));
+
+        let loaded = Context::from_cache_bytes(&bytes).unwrap();
+        assert_eq!(loaded.preset, context.preset);
+        assert_eq!(
+            loaded.public_parameters.commit_key.inner,
+            context.public_parameters.commit_key.inner
+        );
+        assert_eq!(loaded.domain.roots, context.domain.roots);
+        assert_eq!(loaded.domain.generator, context.domain.generator);
+        assert_eq!(loaded.domain.generator_inv, context.domain.generator_inv);
+        assert_eq!(loaded.domain.domain_size, context.domain.domain_size);
+        assert_eq!(
+            loaded.domain.domain_size_inv,
+            context.domain.domain_size_inv
+        );
+        assert_eq!(
+            loaded.public_parameters.opening_key.tau_g2_gen,
+            context.public_parameters.opening_key.tau_g2_gen
+        );
+
+        // Serializing the loaded context gives back the same bytes
+        assert_eq!(loaded.to_cache_bytes(), bytes);
+    }
+
+    // Replaces the contents of `bytes` from `offset` with `replacement`,
+    // and recomputes the checksum if `fix_checksum` is set
+    fn patch(bytes: &[u8], offset: usize, replacement: &[u8], fix_checksum: bool) -> Vec<u8> {
+        let mut patched = bytes.to_vec();
+        patched[offset..offset + replacement.len()].copy_from_slice(replacement);
+        if fix_checksum {
+            let contents_len = patched.len() - CHECKSUM_SIZE;
+            let checksum = Sha256::digest(&patched[..contents_len]);
+            patched[contents_len..].copy_from_slice(&checksum);
+        }
+        patched
+    }
+
+    #[test]
+    fn corrupted_caches_are_rejected() {
+        let bytes = Context::new_insecure_with_preset(Preset::MINIMAL).to_cache_bytes();
+
+        assert_eq!(load(b"not a cache").err(), Some(CacheError::InvalidMagic));
+
+        let mut wrong_version = bytes.clone();
+        wrong_version[CACHE_MAGIC.len() + 3] = 3;
+        assert_eq!(
+            load(&wrong_version).err(),
+            Some(CacheError::UnsupportedVersion {
+                expected: CACHE_VERSION,
+                got: 3
+            })
+        );
+
+        assert_eq!(
+            load(&bytes[..bytes.len() - 1]).err(),
+            Some(CacheError::InvalidLength {
+                expected: bytes.len(),
+                got: bytes.len() - 1
+            })
+        );
+
+        let mut wrong_size = bytes.clone();
+        wrong_size[HEADER_SIZE - 1] = 3;
+        assert_eq!(load(&wrong_size).err(), Some(CacheError::InvalidContents));
+
+        // Points which are not on the curve are rejected, whether or not the checksum matches
+        let mut not_on_curve = bytes[HEADER_SIZE..HEADER_SIZE + G1_UNCOMPRESSED_SIZE].to_vec();
+        not_on_curve[G1_UNCOMPRESSED_SIZE - 1] ^= 1;
+        for fix_checksum in [false, true] {
+            let corrupted = patch(&bytes, HEADER_SIZE, &not_on_curve, fix_checksum);
+            assert_eq!(load(&corrupted).err(), Some(CacheError::InvalidContents));
+        }
+    }
+
+    #[test]
+    fn checksum_mismatch_falls_back_to_validation() {
+        let context = Context::new_insecure_with_preset(Preset::MINIMAL);
+        let bytes = context.to_cache_bytes();
+
+        // Only the checksum is corrupted, so the validated contents are loaded as they are
+        let mut wrong_checksum = bytes.clone();
+        *wrong_checksum.last_mut().unwrap() ^= 1;
+        let loaded = load(&wrong_checksum).unwrap();
+        assert_eq!(
+            loaded.public_parameters.commit_key.inner,
+            context.public_parameters.commit_key.inner
+        );
+
+        // The point (4, y) is on the curve, but not in the prime order subgroup
+        let mut compressed = [0u8; 48];
+        compressed[0] = 0x80;
+        compressed[47] = 4;
+        let not_in_subgroup: G1Point =
+            Option::from(G1Point::from_compressed_unchecked(&compressed)).unwrap();
+        let not_in_subgroup = not_in_subgroup.to_uncompressed();
+
+        // It is only accepted when the checksum matches, since that skips the subgroup check
+        let trusted = patch(&bytes, HEADER_SIZE, &not_in_subgroup, true);
+        assert!(load(&trusted).is_ok());
+        let untrusted = patch(&bytes, HEADER_SIZE, &not_in_subgroup, false);
+        assert_eq!(load(&untrusted).err(), Some(CacheError::InvalidContents));
+    }
+}
@@ -1,10 +1,41 @@
-/// Each blob will hold 2^12 field elements.
+/// On mainnet, each blob will hold 2^12 field elements.
+///
+/// Other sizes can be used through `Preset`.
 pub const FIELD_ELEMENTS_PER_BLOB: usize = 4096;
 
 /// Each field element will be 32 bytes in size.
 pub const FIELD_ELEMENT_SIZE: usize = 32;
 
+/// The number of bytes needed to represent a blob on mainnet.
+pub const BYTES_PER_BLOB: usize = FIELD_ELEMENTS_PER_BLOB * FIELD_ELEMENT_SIZE;
+
 /// While the trusted setup has not been completed
 /// This is the tau value that will be used as a mock
 /// It is not secure to use this in production.
 pub const SECRET_TAU: u64 = 1337;
+
+/// Domain separator used to derive the evaluation challenge
+/// for blob proofs.
+pub const FIAT_SHAMIR_PROTOCOL_DOMAIN: &[u8; 16] = b"FSBLOBVERIFY_V1_";
+
+/// Domain separator used to derive the random challenge
+/// for batch verification of blob proofs.
+pub const RANDOM_CHALLENGE_KZG_BATCH_DOMAIN: &[u8; 16] = b"RCKZGBATCH___V1_";
+
+/// The version byte that is prepended to the hash of a KZG commitment
+/// to form its versioned hash.
+pub const VERSIONED_HASH_VERSION_KZG: u8 = 0x01;
+
+/// The modulus of the scalar field, as a 32 byte big-endian integer.
+pub const BLS_MODULUS: [u8; 32] = [
+    0x73, 0xed, 0xa7, 0x53, 0x29, 0x9d, 0x7d, 0x48, 0x33, 0x39, 0xd8, 0x08, 0x09, 0xa1, 0xd8, 0x05,
+    0x53, 0xbd, 0xa4, 0x02, 0xff, 0xfe, 0x5b, 0xfe, 0xff, 0xff, 0xff, 0xff, 0x00, 0x00, 0x00, 0x01,
+];
+
+/// The size of the input to the point evaluation precompile:
+/// versioned hash, z, y, commitment and proof.
+pub const POINT_EVALUATION_INPUT_SIZE: usize = 192;
+
+/// The size of the output of the point evaluation precompile:
+/// `FIELD_ELEMENTS_PER_BLOB` and `BLS_MODULUS` as big-endian uint256s.
+pub const POINT_EVALUATION_OUTPUT_SIZE: usize = 64;
@@ -0,0 +1,133 @@
+use crate::{CacheError, TrustedSetupError};
+
+/// Errors that can occur when decoding a compressed G1 point
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PointDecodingError {
+    /// The bytes are not a valid compressed encoding of a point on the curve
+    InvalidEncoding,
+    /// The point is on the curve, but not in the prime order subgroup
+    NotInSubgroup,
+    /// The point is the identity, which the `G1DecodingPolicy` does not allow
+    Identity,
+}
+
+/// Errors that can occur when calling a method on `Context`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum KzgError {
+    /// The blob does not have the expected number of bytes
+    InvalidBlobLength { expected: usize, got: usize },
+    /// A preset must have a power of two number of field elements, and at most 2^32 of them
+    UnsupportedBlobSize { field_elements_per_blob: usize },
+    /// The field element at `index` in the blob is not a canonical scalar
+    NonCanonicalFieldElement { index: usize },
+    /// The scalar passed as `argument` is not canonical
+    NonCanonicalScalar { argument: &'static str },
+    /// The point passed as `argument` could not be decoded
+    InvalidPoint {
+        argument: &'static str,
+        error: PointDecodingError,
+    },
+    /// The number of items in `argument` does not match the number of blobs
+    LengthMismatch {
+        argument: &'static str,
+        expected: usize,
+        got: usize,
+    },
+    /// The commitment at `index` does not hash to the versioned hash at `index`
+    VersionedHashMismatch { index: usize },
+    /// The input to the point evaluation precompile does not have the expected number of bytes
+    InvalidPrecompileInputLength { expected: usize, got: usize },
+    /// The commitment passed to the point evaluation precompile does not hash to the versioned hash
+    PrecompileVersionedHashMismatch,
+    /// The proof was well formed, but did not verify
+    InvalidProof,
+    /// An aggregated proof was requested for no blobs
+    EmptyBatch,
+    /// The item at `index` in a batch was invalid
+    InvalidBatchElement { index: usize, error: Box<KzgError> },
+    /// The trusted setup could not be loaded
+    TrustedSetup(TrustedSetupError),
+    /// The context could not be loaded from its cache file
+    Cache(CacheError),
+}
+
+impl From<TrustedSetupError> for KzgError {
+    fn from(error: TrustedSetupError) -> Self {
+        KzgError::TrustedSetup(error)
+    }
+}
+
+impl From<CacheError> for KzgError {
+    fn from(error: CacheError) -> Self {
+        KzgError::Cache(error)
+    }
+}
+
+impl std::fmt::Display for PointDecodingError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PointDecodingError::InvalidEncoding => write!(f, "invalid encoding"),
+            PointDecodingError::NotInSubgroup => write!(f, "not in the prime order subgroup"),
+            PointDecodingError::Identity => write!(f, "the identity is not allowed"),
+        }
+    }
+}
+
+impl std::fmt::Display for KzgError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            KzgError::InvalidBlobLength { expected, got } => {
+                write!(f, "blob has {got} bytes, expected {expected}")
+            }
+            KzgError::UnsupportedBlobSize {
+                field_elements_per_blob,
+            } => write!(
+                f,
+                "blobs with {field_elements_per_blob} field elements are not supported"
+            ),
+            KzgError::NonCanonicalFieldElement { index } => {
+                write!(f, "field element {index} in the blob is not canonical")
+            }
+            KzgError::NonCanonicalScalar { argument } => {
+                write!(f, "`{argument}` is not a canonical scalar")
+            }
+            KzgError::InvalidPoint { argument, error } => {
+                write!(f, "`{argument}` is not a valid point: {error}")
+            }
+            KzgError::LengthMismatch {
+                argument,
+                expected,
+                got,
+            } => write!(f, "`{argument}` has {got} items, expected {expected}"),
+            KzgError::VersionedHashMismatch { index } => {
+                write!(f, "commitment {index} does not match its versioned hash")
+            }
+            KzgError::InvalidPrecompileInputLength { expected, got } => {
+                write!(f, "precompile input has {got} bytes, expected {expected}")
+            }
+            KzgError::PrecompileVersionedHashMismatch => {
+                write!(f, "commitment does not match the versioned hash")
+            }
+            KzgError::InvalidProof => write!(f, "proof is invalid"),
+            KzgError::EmptyBatch => write!(f, "cannot aggregate an empty batch of blobs"),
+            KzgError::InvalidBatchElement { index, error } => {
+                write!(f, "item {index} in the batch is invalid: {error}")
+            }
+            KzgError::TrustedSetup(error) => write!(f, "{error}"),
+            KzgError::Cache(error) => write!(f, "{error}"),
+        }
+    }
+}
+
+impl std::error::Error for PointDecodingError {}
+
+impl std::error::Error for KzgError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            KzgError::InvalidBatchElement { error, .. } => Some(error.as_ref()),
+            KzgError::TrustedSetup(error) => Some(error),
+            KzgError::Cache(error) => Some(error),
+            _ => None,
+        }
+    }
+}
@@ -1,15 +1,40 @@
+mod blob;
+mod cache;
 pub mod constants;
+mod error;
 mod permutation;
+mod point_decoding;
+mod precompile;
+mod preset;
+mod shared;
+mod trusted_setup;
+mod versioned_hash;
+mod workspace;
 
+pub use blob::Blob;
+pub use cache::CacheError;
 use crypto::{
-    Domain, G1Point, Polynomial, PublicParameters, G1_POINT_SERIALIZED_SIZE,
+    AggregatedKZG, Domain, G1Point, Proof, PublicParameters, Transcript, G1_POINT_SERIALIZED_SIZE,
     G2_POINT_SERIALIZED_SIZE, SCALAR_SERIALIZED_SIZE,
 };
+pub use error::{KzgError, PointDecodingError};
+use group::prime::PrimeCurveAffine;
 use permutation::Permutable;
+pub use point_decoding::G1DecodingPolicy;
+use point_decoding::{deserialize_g1_point, deserialize_g1_point_unchecked};
+pub use preset::Preset;
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+pub use trusted_setup::TrustedSetupError;
+use trusted_setup::{DeserializeG1Point, TrustedSetup, ValidatedTrustedSetup};
+pub use versioned_hash::{kzg_to_versioned_hash, VersionedHash};
+pub use workspace::ProverWorkspace;
 
 pub struct Context {
     public_parameters: PublicParameters,
     domain: Domain,
+    preset: Preset,
+    decoding_policy: G1DecodingPolicy,
 }
 
 use crypto::Scalar;
@@ -20,10 +45,15 @@ pub type SerializedG1Point = [u8; G1_POINT_SERIALIZED_SIZE];
 pub type SerializedG2Point = [u8; G2_POINT_SERIALIZED_SIZE];
 pub type KZGCommitmentBytes = SerializedG1Point;
 pub type KZGProofBytes = SerializedG1Point;
+pub type KZGWitnessBytes = SerializedG1Point;
 
 impl Context {
     pub fn new_insecure() -> Self {
-        let num_g1 = constants::FIELD_ELEMENTS_PER_BLOB;
+        Context::new_insecure_with_preset(Preset::MAINNET)
+    }
+
+    pub fn new_insecure_with_preset(preset: Preset) -> Self {
+        let num_g1 = preset.field_elements_per_blob();
 
         let secret = constants::SECRET_TAU;
 
@@ -33,30 +63,154 @@ impl Context {
         Context {
             public_parameters: public_parameters.permute(),
             domain: domain.permute(),
+            preset,
+            decoding_policy: G1DecodingPolicy::default(),
         }
     }
 
-    pub fn from_json_str(_trusted_setup_json: String) -> Self {
-        todo!("The trusted setup has not been completed. For testing use the `insecure` method")
+    /// Creates a context from the trusted setup JSON file produced by the ceremony.
+    ///
+    /// Every point is decompressed and checked to be in the prime order subgroup.
+    pub fn from_json_str(trusted_setup_json: &str) -> Result<Self, KzgError> {
+        Context::from_json_str_with_preset(trusted_setup_json, Preset::MAINNET)
+    }
+
+    /// Like `Context::from_json_str`, but for blobs of the size given by `preset`.
+    ///
+    /// The trusted setup must have exactly one G1 point for each field element in a blob.
+    pub fn from_json_str_with_preset(
+        trusted_setup_json: &str,
+        preset: Preset,
+    ) -> Result<Self, KzgError> {
+        Context::from_json_str_with_decoder(trusted_setup_json, preset, deserialize_g1_point)
+    }
+
+    /// Like `Context::from_json_str`, but the G1 points are only checked to be on the curve,
+    /// and not to be in the prime order subgroup, which makes loading much faster.
+    ///
+    /// Only use this for a trusted setup file from a trusted local source,
+    /// for example one that is shipped with the binary.
+    /// Points passed to the methods on `Context` are always fully checked.
+    pub fn from_json_str_unchecked(trusted_setup_json: &str) -> Result<Self, KzgError> {
+        Context::from_json_str_unchecked_with_preset(trusted_setup_json, Preset::MAINNET)
+    }
+
+    /// Like `Context::from_json_str_unchecked`, but for blobs of the size given by `preset`.
+    pub fn from_json_str_unchecked_with_preset(
+        trusted_setup_json: &str,
+        preset: Preset,
+    ) -> Result<Self, KzgError> {
+        Context::from_json_str_with_decoder(
+            trusted_setup_json,
+            preset,
+            deserialize_g1_point_unchecked,
+        )
+    }
+
+    fn from_json_str_with_decoder(
+        trusted_setup_json: &str,
+        preset: Preset,
+        deserialize_g1: DeserializeG1Point,
+    ) -> Result<Self, KzgError> {
+        let trusted_setup =
+            TrustedSetup::from_json_str(trusted_setup_json)?.validate(preset, deserialize_g1)?;
+
+        Ok(Context::from_validated_trusted_setup(trusted_setup, preset))
+    }
+
+    /// Sets the policy used to decode commitments and proofs.
+    ///
+    /// The default is `G1DecodingPolicy::SPEC`.
+    pub fn with_decoding_policy(mut self, decoding_policy: G1DecodingPolicy) -> Self {
+        self.decoding_policy = decoding_policy;
+        self
+    }
+
+    /// The preset this context was built for
+    pub fn preset(&self) -> Preset {
+        self.preset
+    }
+
+    fn from_validated_trusted_setup(trusted_setup: ValidatedTrustedSetup, preset: Preset) -> Self {
+        let g1_lagrange = trusted_setup.g1_lagrange;
+        let g2_gen = trusted_setup.g2_monomial[0];
+        let tau_g2_gen = trusted_setup.g2_monomial[1];
+
+        let domain = Domain::new(preset.field_elements_per_blob());
+        let public_parameters = PublicParameters::from_lagrange_srs(
+            g1_lagrange,
+            G1Point::generator(),
+            g2_gen,
+            tau_g2_gen,
+        );
+
+        Context {
+            public_parameters: public_parameters.permute(),
+            domain: domain.permute(),
+            preset,
+            decoding_policy: G1DecodingPolicy::default(),
+        }
     }
 
-    // setup_g1: G1 elements in monomial form
-    // setup_g1_lagrange: G1 elements in lagrange form
-    // setup_g2: G2 elements, For 4844, we only need 2 of these elements
+    /// Creates a context from compressed points, for the mainnet preset.
+    ///
+    /// setup_g1: G1 elements in monomial form. If supplied, they are checked against `setup_g1_lagrange`
+    /// setup_g1_lagrange: G1 elements in lagrange form, in their natural order
+    /// setup_g2: G2 elements, For 4844, we only need 2 of these elements
     pub fn from_hex(
-        setup_g1: Vec<SerializedG1Point>,
+        setup_g1: Option<Vec<SerializedG1Point>>,
         setup_g1_lagrange: Vec<SerializedG1Point>,
         setup_g2: Vec<SerializedG2Point>,
-    ) -> Option<Self> {
-        todo!("The trusted setup has not been completed. For testing use the `insecure` method")
+    ) -> Result<Self, KzgError> {
+        Context::from_hex_with_preset(setup_g1, setup_g1_lagrange, setup_g2, Preset::MAINNET)
+    }
+
+    /// Like `Context::from_hex`, but for blobs of the size given by `preset`.
+    ///
+    /// There must be exactly one lagrange point for each field element in a blob.
+    pub fn from_hex_with_preset(
+        setup_g1: Option<Vec<SerializedG1Point>>,
+        setup_g1_lagrange: Vec<SerializedG1Point>,
+        setup_g2: Vec<SerializedG2Point>,
+        preset: Preset,
+    ) -> Result<Self, KzgError> {
+        let trusted_setup = ValidatedTrustedSetup::from_compressed(
+            setup_g1.as_deref(),
+            &setup_g1_lagrange,
+            &setup_g2,
+            preset,
+            deserialize_g1_point,
+        )?;
+
+        Ok(Context::from_validated_trusted_setup(trusted_setup, preset))
     }
 
-    pub fn blob_to_kzg_commitment(&self, blob_bytes: BlobBytes) -> Option<KZGCommitmentBytes> {
-        let polynomial = blob_bytes_to_polynomial(blob_bytes)?;
+    pub fn blob_to_kzg_commitment(&self, blob: &Blob) -> Result<KZGCommitmentBytes, KzgError> {
+        self.check_blob_size(blob)?;
+        let polynomial = blob.to_polynomial();
 
         let commitment = self.public_parameters.commit_key.commit(&polynomial);
 
-        Some(commitment.to_compressed())
+        Ok(commitment.to_compressed())
+    }
+
+    /// Commits to each blob, returning the commitments in the same order.
+    ///
+    /// With the `parallel` feature, the blobs are committed to on the rayon thread pool.
+    /// Each blob gets its own result, so an invalid blob does not stop the others
+    /// from being committed to.
+    pub fn blobs_to_kzg_commitments(
+        &self,
+        blobs: &[Blob],
+    ) -> Vec<Result<KZGCommitmentBytes, KzgError>> {
+        let commit = |blob: &Blob| self.blob_to_kzg_commitment(blob);
+
+        #[cfg(feature = "rayon")]
+        let commitments = blobs.par_iter().map(commit).collect();
+        #[cfg(not(feature = "rayon"))]
+        let commitments = blobs.iter().map(commit).collect();
+
+        commitments
     }
 
     pub fn verify_kzg_proof(
@@ -65,49 +219,971 @@ impl Context {
         input_point: SerializedScalar,
         claimed_value: SerializedScalar,
         proof: KZGProofBytes,
-    ) -> Option<bool> {
-        let input_point = bytes_to_scalar(&input_point)?;
-        let claimed_value = bytes_to_scalar(&claimed_value)?;
-        let poly_commitment = bytes_to_point(&commitment)?;
-        let quotient_commitment = bytes_to_point(&proof)?;
+    ) -> Result<bool, KzgError> {
+        let input_point = bytes_to_scalar(&input_point, "input_point")?;
+        let claimed_value = bytes_to_scalar(&claimed_value, "claimed_value")?;
+        let poly_commitment = self.bytes_to_commitment(&commitment)?;
+        let quotient_commitment = self.bytes_to_proof(&proof, "proof")?;
 
-        Some(self.public_parameters.opening_key.verify(
+        Ok(self.public_parameters.opening_key.verify(
             input_point,
             claimed_value,
             poly_commitment,
             quotient_commitment,
         ))
     }
-    pub fn compute_kzg_proof() {
-        todo!("this is a helper method for the verification method")
+
+    /// Evaluates the polynomial represented by `blob` at `input_point`,
+    /// without computing a proof.
+    ///
+    /// The blob holds the evaluations of the polynomial over the bit-reversed domain,
+    /// so if `input_point` is the i'th element of that domain, this returns
+    /// the i'th field element in the blob.
+    pub fn evaluate_blob(
+        &self,
+        blob: &Blob,
+        input_point: SerializedScalar,
+    ) -> Result<SerializedScalar, KzgError> {
+        self.check_blob_size(blob)?;
+        let polynomial = blob.to_polynomial();
+        let input_point = bytes_to_scalar(&input_point, "input_point")?;
+
+        let output_point = polynomial.evaluate(input_point, &self.domain);
+
+        Ok(scalar_to_bytes(&output_point))
     }
-}
 
-fn blob_bytes_to_polynomial(bytes: Vec<u8>) -> Option<Polynomial> {
-    if bytes.len() % SCALAR_SERIALIZED_SIZE != 0 {
-        return None;
+    /// Computes a KZG proof that the polynomial represented by `blob`
+    /// evaluates to `y` at `input_point`.
+    ///
+    /// Returns the proof along with the claimed value `y`.
+    pub fn compute_kzg_proof(
+        &self,
+        blob: &Blob,
+        input_point: SerializedScalar,
+    ) -> Result<(KZGProofBytes, SerializedScalar), KzgError> {
+        self.check_blob_size(blob)?;
+        let polynomial = blob.to_polynomial();
+        let input_point = bytes_to_scalar(&input_point, "input_point")?;
+
+        let commit_key = &self.public_parameters.commit_key;
+        let poly_comm = commit_key.commit(&polynomial);
+
+        let proof = Proof::create(
+            commit_key,
+            &polynomial,
+            poly_comm,
+            input_point,
+            &self.domain,
+        );
+
+        Ok((
+            proof.quotient_commitment.to_compressed(),
+            scalar_to_bytes(&proof.output_point),
+        ))
+    }
+
+    /// Computes a KZG proof for the blob, at an evaluation point
+    /// derived from the blob and its commitment using Fiat-Shamir.
+    pub fn compute_blob_kzg_proof(
+        &self,
+        blob: &Blob,
+        commitment: KZGCommitmentBytes,
+    ) -> Result<KZGProofBytes, KzgError> {
+        self.check_blob_size(blob)?;
+        let polynomial = blob.to_polynomial();
+        let poly_comm = self.bytes_to_commitment(&commitment)?;
+        let evaluation_challenge = compute_challenge(blob, &commitment);
+
+        let proof = Proof::create(
+            &self.public_parameters.commit_key,
+            &polynomial,
+            poly_comm,
+            evaluation_challenge,
+            &self.domain,
+        );
+
+        Ok(proof.quotient_commitment.to_compressed())
     }
 
-    assert!(!bytes.is_empty(), "blobs should have a fixed non-zero size");
+    /// Verifies a proof created by `compute_blob_kzg_proof`.
+    pub fn verify_blob_kzg_proof(
+        &self,
+        blob: &Blob,
+        commitment: KZGCommitmentBytes,
+        proof: KZGProofBytes,
+    ) -> Result<bool, KzgError> {
+        self.check_blob_size(blob)?;
+        let polynomial = blob.to_polynomial();
+        let poly_comm = self.bytes_to_commitment(&commitment)?;
+        let quotient_commitment = self.bytes_to_proof(&proof, "proof")?;
+        let evaluation_challenge = compute_challenge(blob, &commitment);
+
+        let output_point = polynomial.evaluate(evaluation_challenge, &self.domain);
 
-    let num_scalars = bytes.len() / SCALAR_SERIALIZED_SIZE;
+        Ok(self.public_parameters.opening_key.verify(
+            evaluation_challenge,
+            output_point,
+            poly_comm,
+            quotient_commitment,
+        ))
+    }
+
+    /// Verifies multiple proofs created by `compute_blob_kzg_proof`.
+    ///
+    /// This is more efficient than calling `verify_blob_kzg_proof` for each
+    /// blob, as only a single pairing check is needed.
+    ///
+    /// Returns false if any of the proofs are invalid.
+    pub fn verify_blob_kzg_proof_batch(
+        &self,
+        blobs: &[Blob],
+        commitments: &[KZGCommitmentBytes],
+        proofs: &[KZGProofBytes],
+    ) -> Result<bool, KzgError> {
+        check_batch_length("commitments", blobs.len(), commitments.len())?;
+        check_batch_length("proofs", blobs.len(), proofs.len())?;
+
+        let num_blobs = blobs.len();
+        let mut poly_comms = Vec::with_capacity(num_blobs);
+        let mut quotient_comms = Vec::with_capacity(num_blobs);
+        let mut evaluation_challenges = Vec::with_capacity(num_blobs);
+        let mut output_points = Vec::with_capacity(num_blobs);
+
+        for (index, ((blob, commitment), proof)) in
+            blobs.iter().zip(commitments).zip(proofs).enumerate()
+        {
+            let decode = || -> Result<_, KzgError> {
+                self.check_blob_size(blob)?;
+                Ok((
+                    self.bytes_to_commitment(commitment)?,
+                    self.bytes_to_proof(proof, "proof")?,
+                ))
+            };
+            let (poly_comm, quotient_comm) =
+                decode().map_err(|error| KzgError::InvalidBatchElement {
+                    index,
+                    error: Box::new(error),
+                })?;
+
+            let polynomial = blob.to_polynomial();
+            let evaluation_challenge = compute_challenge(blob, commitment);
+
+            poly_comms.push(poly_comm);
+            quotient_comms.push(quotient_comm);
+            output_points.push(polynomial.evaluate(evaluation_challenge, &self.domain));
+            evaluation_challenges.push(evaluation_challenge);
+        }
+
+        let batch_challenge = compute_batch_challenge(
+            self.preset.field_elements_per_blob(),
+            commitments,
+            &evaluation_challenges,
+            &output_points,
+            proofs,
+        );
+
+        Ok(self.public_parameters.opening_key.verify_batch(
+            &evaluation_challenges,
+            &output_points,
+            &poly_comms,
+            &quotient_comms,
+            batch_challenge,
+        ))
+    }
+
+    /// Checks that each commitment is a valid point, and that it hashes
+    /// to the versioned hash at the same index.
+    ///
+    /// Returns an error for the first commitment that does not.
+    pub fn verify_commitments_against_versioned_hashes(
+        &self,
+        commitments: &[KZGCommitmentBytes],
+        versioned_hashes: &[VersionedHash],
+    ) -> Result<(), KzgError> {
+        check_batch_length(
+            "versioned_hashes",
+            commitments.len(),
+            versioned_hashes.len(),
+        )?;
+
+        for (index, (commitment, versioned_hash)) in
+            commitments.iter().zip(versioned_hashes).enumerate()
+        {
+            self.bytes_to_commitment(commitment).map_err(|error| {
+                KzgError::InvalidBatchElement {
+                    index,
+                    error: Box::new(error),
+                }
+            })?;
+
+            if &kzg_to_versioned_hash(commitment) != versioned_hash {
+                return Err(KzgError::VersionedHashMismatch { index });
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Computes a single proof for all of the blobs.
+    ///
+    /// Returns the proof along with the commitment to each blob.
+    /// There must be at least one blob.
+    pub fn compute_aggregated_kzg_proof(
+        &self,
+        blobs: &[Blob],
+    ) -> Result<(KZGWitnessBytes, Vec<KZGCommitmentBytes>), KzgError> {
+        check_batch_not_empty(blobs)?;
+        self.check_batch_blob_sizes(blobs)?;
+        let polys: Vec<_> = blobs.iter().map(Blob::to_polynomial).collect();
+
+        let commit_key = &self.public_parameters.commit_key;
+        let poly_comms = commit_key.commit_multiple(&polys);
+        let comms_bytes = poly_comms.iter().map(G1Point::to_compressed).collect();
+
+        let agg = AggregatedKZG::from_polys(polys, poly_comms);
+        let witness = agg.create(commit_key, &self.domain);
+
+        Ok((witness.to_compressed(), comms_bytes))
+    }
+
+    /// Verifies a proof created by `compute_aggregated_kzg_proof`.
+    ///
+    /// No proof can be created for an empty batch, so an empty batch is rejected.
+    pub fn verify_aggregated_kzg_proof(
+        &self,
+        blobs: &[Blob],
+        commitments: &[KZGCommitmentBytes],
+        witness: KZGWitnessBytes,
+    ) -> Result<bool, KzgError> {
+        check_batch_not_empty(blobs)?;
+        check_batch_length("commitments", blobs.len(), commitments.len())?;
+        self.check_batch_blob_sizes(blobs)?;
+
+        let polys: Vec<_> = blobs.iter().map(Blob::to_polynomial).collect();
+        let poly_comms = commitments
+            .iter()
+            .enumerate()
+            .map(|(index, commitment)| {
+                self.bytes_to_commitment(commitment).map_err(|error| {
+                    KzgError::InvalidBatchElement {
+                        index,
+                        error: Box::new(error),
+                    }
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let witness = self.bytes_to_proof(&witness, "witness")?;
+
+        let agg = AggregatedKZG::from_polys(polys, poly_comms);
+        Ok(agg.verify(witness, &self.public_parameters.opening_key, &self.domain))
+    }
+
+    fn check_blob_size(&self, blob: &Blob) -> Result<(), KzgError> {
+        let expected = self.preset.bytes_per_blob();
+        if blob.as_bytes().len() != expected {
+            return Err(KzgError::InvalidBlobLength {
+                expected,
+                got: blob.as_bytes().len(),
+            });
+        }
+        Ok(())
+    }
+
+    fn check_batch_blob_sizes(&self, blobs: &[Blob]) -> Result<(), KzgError> {
+        for (index, blob) in blobs.iter().enumerate() {
+            self.check_blob_size(blob)
+                .map_err(|error| KzgError::InvalidBatchElement {
+                    index,
+                    error: Box::new(error),
+                })?;
+        }
+        Ok(())
+    }
+
+    fn bytes_to_commitment(&self, point_bytes: &KZGCommitmentBytes) -> Result<G1Point, KzgError> {
+        self.decoding_policy
+            .decode_commitment(point_bytes)
+            .map_err(|error| KzgError::InvalidPoint {
+                argument: "commitment",
+                error,
+            })
+    }
+
+    fn bytes_to_proof(
+        &self,
+        point_bytes: &KZGProofBytes,
+        argument: &'static str,
+    ) -> Result<G1Point, KzgError> {
+        self.decoding_policy
+            .decode_proof(point_bytes)
+            .map_err(|error| KzgError::InvalidPoint { argument, error })
+    }
+}
+
+// Computes the evaluation point for a blob proof, by hashing
+// the blob and its commitment
+fn compute_challenge(blob: &Blob, commitment: &KZGCommitmentBytes) -> Scalar {
+    let mut transcript = Transcript::with_domain_separator(constants::FIAT_SHAMIR_PROTOCOL_DOMAIN);
+
+    // The degree of the polynomial is encoded as a 16 byte big-endian integer
+    let degree_poly = (blob.num_field_elements() as u128).to_be_bytes();
+    transcript.append_bytes(&degree_poly);
+
+    transcript.append_bytes(blob.as_bytes());
+    transcript.append_bytes(commitment);
+
+    transcript.challenge()
+}
+
+// Computes the challenge used to combine multiple proofs into
+// a single pairing check.
+//
+// This does not need to be derived using Fiat-Shamir, it only needs
+// to be unpredictable to the prover. Hashing the proof data makes it deterministic.
+fn compute_batch_challenge(
+    field_elements_per_blob: usize,
+    commitments: &[KZGCommitmentBytes],
+    input_points: &[Scalar],
+    output_points: &[Scalar],
+    proofs: &[KZGProofBytes],
+) -> Scalar {
+    let mut transcript =
+        Transcript::with_domain_separator(constants::RANDOM_CHALLENGE_KZG_BATCH_DOMAIN);
+
+    let degree_poly = (field_elements_per_blob as u64).to_be_bytes();
+    let num_commitments = (commitments.len() as u64).to_be_bytes();
+    transcript.append_bytes(&degree_poly);
+    transcript.append_bytes(&num_commitments);
+
+    for i in 0..commitments.len() {
+        transcript.append_bytes(&commitments[i]);
+        transcript.append_bytes(&scalar_to_bytes(&input_points[i]));
+        transcript.append_bytes(&scalar_to_bytes(&output_points[i]));
+        transcript.append_bytes(&proofs[i]);
+    }
+
+    transcript.challenge()
+}
 
-    let mut polynomial_inner = Vec::with_capacity(num_scalars);
-    let iter = bytes.chunks_exact(SCALAR_SERIALIZED_SIZE);
-    for chunk in iter {
-        let chunk32: SerializedScalar = chunk
-            .try_into()
-            .expect("infallible: since the length of the bytes vector is a multiple of 32");
-        polynomial_inner.push(bytes_to_scalar(&chunk32)?)
+fn check_batch_not_empty(blobs: &[Blob]) -> Result<(), KzgError> {
+    if blobs.is_empty() {
+        return Err(KzgError::EmptyBatch);
     }
+    Ok(())
+}
 
-    Polynomial::new(polynomial_inner).into()
+fn check_batch_length(
+    argument: &'static str,
+    num_blobs: usize,
+    num_items: usize,
+) -> Result<(), KzgError> {
+    if num_blobs != num_items {
+        return Err(KzgError::LengthMismatch {
+            argument,
+            expected: num_blobs,
+            got: num_items,
+        });
+    }
+    Ok(())
 }
-fn bytes_to_point(point_bytes: &SerializedG1Point) -> Option<G1Point> {
-    let ct_point = G1Point::from_compressed(&point_bytes);
-    bool::from(ct_point.is_some()).then(|| ct_point.unwrap())
+
+fn bytes_to_scalar(
+    scalar_bytes: &SerializedScalar,
+    argument: &'static str,
+) -> Result<Scalar, KzgError> {
+    deserialize_scalar(scalar_bytes).ok_or(KzgError::NonCanonicalScalar { argument })
 }
-fn bytes_to_scalar(scalar_bytes: &SerializedScalar) -> Option<Scalar> {
-    let ct_scalar = Scalar::from_bytes_le(scalar_bytes);
+// Scalars are encoded as 32 byte big-endian integers, as in the consensus specs
+fn deserialize_scalar(scalar_bytes: &SerializedScalar) -> Option<Scalar> {
+    let ct_scalar = Scalar::from_bytes_be(scalar_bytes);
     bool::from(ct_scalar.is_some()).then(|| ct_scalar.unwrap())
 }
+fn scalar_to_bytes(scalar: &Scalar) -> SerializedScalar {
+    scalar.to_bytes_be()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constants::FIELD_ELEMENTS_PER_BLOB;
+
+    fn dummy_blob_bytes() -> BlobBytes {
+        (0..FIELD_ELEMENTS_PER_BLOB as u64)
+            .flat_map(|i| scalar_to_bytes(&Scalar::from(i * 31 + 7)))
+            .collect()
+    }
+
+    fn dummy_blob() -> Blob {
+        Blob::from_bytes(dummy_blob_bytes()).unwrap()
+    }
+
+    // Returns a different blob for each value of `i`
+    fn nth_dummy_blob(i: u8) -> Blob {
+        let mut blob_bytes = dummy_blob_bytes();
+        blob_bytes[0] = i;
+        Blob::from_bytes(blob_bytes).unwrap()
+    }
+
+    #[test]
+    fn scalars_are_big_endian() {
+        let mut one = [0u8; 32];
+        one[31] = 1;
+        assert_eq!(scalar_to_bytes(&Scalar::from(1u64)), one);
+        assert_eq!(bytes_to_scalar(&one, "one"), Ok(Scalar::from(1u64)));
+
+        // The modulus is not canonical, but the modulus minus one is
+        let mut modulus_minus_one =
+            hex::decode("73eda753299d7d483339d80809a1d80553bda402fffe5bfeffffffff00000000")
+                .unwrap();
+        assert_eq!(
+            bytes_to_scalar(&modulus_minus_one.clone().try_into().unwrap(), "z"),
+            Ok(-Scalar::from(1u64))
+        );
+        modulus_minus_one[31] = 1;
+        assert_eq!(
+            bytes_to_scalar(&modulus_minus_one.try_into().unwrap(), "z"),
+            Err(KzgError::NonCanonicalScalar { argument: "z" })
+        );
+    }
+
+    // Returns the trusted setup JSON for the insecure setup with the given preset
+    fn insecure_setup_json(preset: Preset) -> String {
+        let domain = Domain::new(preset.field_elements_per_blob());
+        let public_parameters =
+            PublicParameters::from_secret_insecure(constants::SECRET_TAU, &domain);
+
+        let to_hex = |bytes: &[u8]| format!("0x{}", hex::encode(bytes));
+        let g1_lagrange: Vec<_> = public_parameters
+            .commit_key
+            .inner
+            .iter()
+            .map(|point| to_hex(&point.to_compressed()))
+            .collect();
+        let g2_monomial = vec![
+            to_hex(&public_parameters.opening_key.g2_gen.to_compressed()),
+            to_hex(&public_parameters.opening_key.tau_g2_gen.to_compressed()),
+        ];
+        serde_json::json!({
+            "g1_lagrange": g1_lagrange,
+            "g2_monomial": g2_monomial,
+        })
+        .to_string()
+    }
+
+    #[test]
+    fn from_json_str_matches_insecure() {
+        let json = insecure_setup_json(Preset::MAINNET);
+        let context = Context::from_json_str(&json).unwrap();
+        let insecure_context = Context::new_insecure();
+
+        let blob = dummy_blob();
+        assert_eq!(
+            context.blob_to_kzg_commitment(&blob),
+            insecure_context.blob_to_kzg_commitment(&blob)
+        );
+    }
+
+    #[test]
+    fn from_json_str_with_preset_matches_insecure() {
+        let preset = Preset::MINIMAL;
+        let json = insecure_setup_json(preset);
+        let insecure_context = Context::new_insecure_with_preset(preset);
+        let blob = Blob::from_bytes_with_preset(vec![1; preset.bytes_per_blob()], preset).unwrap();
+        let expected_commitment = insecure_context.blob_to_kzg_commitment(&blob);
+
+        for context in [
+            Context::from_json_str_with_preset(&json, preset).unwrap(),
+            Context::from_json_str_unchecked_with_preset(&json, preset).unwrap(),
+        ] {
+            assert_eq!(context.preset(), preset);
+            assert_eq!(context.blob_to_kzg_commitment(&blob), expected_commitment);
+        }
+
+        // The setup must match the preset, even when the points are not fully checked
+        assert!(Context::from_json_str_unchecked(&json).is_err());
+    }
+
+    #[test]
+    fn smallest_preset() {
+        let preset = Preset::new(1).unwrap();
+        let json = insecure_setup_json(preset);
+        let blob = Blob::from_bytes_with_preset(vec![1; preset.bytes_per_blob()], preset).unwrap();
+
+        for context in [
+            Context::new_insecure_with_preset(preset),
+            Context::from_json_str_with_preset(&json, preset).unwrap(),
+        ] {
+            let commitment = context.blob_to_kzg_commitment(&blob).unwrap();
+            let proof = context.compute_blob_kzg_proof(&blob, commitment).unwrap();
+            assert!(context
+                .verify_blob_kzg_proof(&blob, commitment, proof)
+                .unwrap());
+        }
+    }
+
+    // Returns the compressed monomial, lagrange and G2 points
+    // for the insecure setup with the given preset
+    fn insecure_setup_points(
+        preset: Preset,
+    ) -> (
+        Vec<SerializedG1Point>,
+        Vec<SerializedG1Point>,
+        Vec<SerializedG2Point>,
+    ) {
+        let tau = Scalar::from(constants::SECRET_TAU);
+        let mut tau_power = Scalar::from(1u64);
+        let mut g1_monomial = Vec::with_capacity(preset.field_elements_per_blob());
+        for _ in 0..preset.field_elements_per_blob() {
+            g1_monomial.push(G1Point::from(G1Point::generator() * tau_power).to_compressed());
+            tau_power *= tau;
+        }
+
+        let domain = Domain::new(preset.field_elements_per_blob());
+        let public_parameters =
+            PublicParameters::from_secret_insecure(constants::SECRET_TAU, &domain);
+        let g1_lagrange = public_parameters
+            .commit_key
+            .inner
+            .iter()
+            .map(G1Point::to_compressed)
+            .collect();
+        let g2 = vec![
+            public_parameters.opening_key.g2_gen.to_compressed(),
+            public_parameters.opening_key.tau_g2_gen.to_compressed(),
+        ];
+
+        (g1_monomial, g1_lagrange, g2)
+    }
+
+    #[test]
+    fn from_hex_checks_monomial_against_lagrange() {
+        let (g1_monomial, g1_lagrange, g2) = insecure_setup_points(Preset::MAINNET);
+        let blob = dummy_blob();
+        let expected_commitment = Context::new_insecure().blob_to_kzg_commitment(&blob);
+
+        let context =
+            Context::from_hex(Some(g1_monomial.clone()), g1_lagrange.clone(), g2.clone()).unwrap();
+        assert_eq!(context.blob_to_kzg_commitment(&blob), expected_commitment);
+
+        // The monomial points are optional
+        let context = Context::from_hex(None, g1_lagrange.clone(), g2.clone()).unwrap();
+        assert_eq!(context.blob_to_kzg_commitment(&blob), expected_commitment);
+
+        // Lagrange points that have already been bit-reversed should be rejected
+        let permuted_g1_lagrange = permutation::bit_reversal_permutation(&g1_lagrange);
+        assert_eq!(
+            Context::from_hex(Some(g1_monomial), permuted_g1_lagrange, g2).err(),
+            Some(KzgError::TrustedSetup(
+                TrustedSetupError::MonomialLagrangeMismatch
+            ))
+        );
+    }
+
+    #[test]
+    fn from_hex_with_preset_matches_insecure() {
+        let preset = Preset::MINIMAL;
+        let (g1_monomial, g1_lagrange, g2) = insecure_setup_points(preset);
+        let blob = Blob::from_bytes_with_preset(vec![1; preset.bytes_per_blob()], preset).unwrap();
+        let expected_commitment =
+            Context::new_insecure_with_preset(preset).blob_to_kzg_commitment(&blob);
+
+        let context = Context::from_hex_with_preset(
+            Some(g1_monomial),
+            g1_lagrange.clone(),
+            g2.clone(),
+            preset,
+        )
+        .unwrap();
+        assert_eq!(context.preset(), preset);
+        assert_eq!(context.blob_to_kzg_commitment(&blob), expected_commitment);
+
+        // The setup must match the preset
+        assert!(Context::from_hex(None, g1_lagrange, g2).is_err());
+    }
+
+    #[test]
+    fn invalid_inputs_report_the_reason() {
+        let context = Context::new_insecure();
+        let blob = dummy_blob();
+        let commitment = context.blob_to_kzg_commitment(&blob).unwrap();
+        let input_point = scalar_to_bytes(&Scalar::from(123456789u64));
+        let (proof, claimed_value) = context.compute_kzg_proof(&blob, input_point).unwrap();
+
+        assert_eq!(
+            context.verify_kzg_proof(commitment, input_point, [0xff; 32], proof),
+            Err(KzgError::NonCanonicalScalar {
+                argument: "claimed_value"
+            })
+        );
+
+        // Not a valid encoding, since the compression flag is not set
+        assert_eq!(
+            context.verify_kzg_proof([0; 48], input_point, claimed_value, proof),
+            Err(KzgError::InvalidPoint {
+                argument: "commitment",
+                error: PointDecodingError::InvalidEncoding
+            })
+        );
+
+        // The point (4, y) is on the curve, but not in the prime order subgroup
+        let mut not_in_subgroup = [0u8; 48];
+        not_in_subgroup[0] = 0x80;
+        not_in_subgroup[47] = 4;
+        assert_eq!(
+            context.verify_kzg_proof(commitment, input_point, claimed_value, not_in_subgroup),
+            Err(KzgError::InvalidPoint {
+                argument: "proof",
+                error: PointDecodingError::NotInSubgroup
+            })
+        );
+    }
+
+    #[test]
+    fn identity_commitments_and_proofs() {
+        let context = Context::new_insecure();
+        let identity = G1Point::identity().to_compressed();
+
+        // The zero blob commits to the identity, and so does its proof
+        let zero_blob = Blob::from_bytes(vec![0; constants::BYTES_PER_BLOB]).unwrap();
+        let commitment = context.blob_to_kzg_commitment(&zero_blob).unwrap();
+        let proof = context
+            .compute_blob_kzg_proof(&zero_blob, commitment)
+            .unwrap();
+        assert_eq!(commitment, identity);
+        assert_eq!(proof, identity);
+        assert_eq!(
+            context.verify_blob_kzg_proof(&zero_blob, commitment, proof),
+            Ok(true)
+        );
+
+        let context = context.with_decoding_policy(G1DecodingPolicy {
+            allow_identity_commitment: false,
+            allow_identity_proof: true,
+        });
+        assert_eq!(
+            context.verify_blob_kzg_proof(&zero_blob, commitment, proof),
+            Err(KzgError::InvalidPoint {
+                argument: "commitment",
+                error: PointDecodingError::Identity
+            })
+        );
+
+        let context = context.with_decoding_policy(G1DecodingPolicy {
+            allow_identity_commitment: true,
+            allow_identity_proof: false,
+        });
+        assert_eq!(
+            context.verify_blob_kzg_proof(&zero_blob, commitment, proof),
+            Err(KzgError::InvalidPoint {
+                argument: "proof",
+                error: PointDecodingError::Identity
+            })
+        );
+    }
+
+    #[test]
+    fn minimal_preset() {
+        let context = Context::new_insecure_with_preset(Preset::MINIMAL);
+        assert_eq!(context.preset(), Preset::MINIMAL);
+
+        let blob_bytes = (1..=4u64)
+            .flat_map(|i| scalar_to_bytes(&Scalar::from(i)))
+            .collect();
+        let blob = Blob::from_bytes_with_preset(blob_bytes, Preset::MINIMAL).unwrap();
+
+        let commitment = context.blob_to_kzg_commitment(&blob).unwrap();
+        let proof = context.compute_blob_kzg_proof(&blob, commitment).unwrap();
+        assert!(context
+            .verify_blob_kzg_proof(&blob, commitment, proof)
+            .unwrap());
+        assert!(context
+            .verify_blob_kzg_proof_batch(std::slice::from_ref(&blob), &[commitment], &[proof])
+            .unwrap());
+
+        // Blobs for other presets are rejected
+        let mainnet_blob = dummy_blob();
+        assert_eq!(
+            context.blob_to_kzg_commitment(&mainnet_blob),
+            Err(KzgError::InvalidBlobLength {
+                expected: Preset::MINIMAL.bytes_per_blob(),
+                got: constants::BYTES_PER_BLOB
+            })
+        );
+        assert_eq!(
+            context.verify_blob_kzg_proof_batch(
+                &[blob, mainnet_blob],
+                &[commitment, commitment],
+                &[proof, proof]
+            ),
+            Err(KzgError::InvalidBatchElement {
+                index: 1,
+                error: Box::new(KzgError::InvalidBlobLength {
+                    expected: Preset::MINIMAL.bytes_per_blob(),
+                    got: constants::BYTES_PER_BLOB
+                })
+            })
+        );
+    }
+
+    #[test]
+    fn compute_kzg_proof_round_trip() {
+        let context = Context::new_insecure();
+        let blob = dummy_blob();
+
+        let commitment = context.blob_to_kzg_commitment(&blob).unwrap();
+
+        let input_point = scalar_to_bytes(&Scalar::from(123456789u64));
+        let (proof, claimed_value) = context.compute_kzg_proof(&blob, input_point).unwrap();
+
+        assert!(context
+            .verify_kzg_proof(commitment, input_point, claimed_value, proof)
+            .unwrap());
+
+        // Changing the claimed value should cause the proof to fail
+        let wrong_value = scalar_to_bytes(
+            &(bytes_to_scalar(&claimed_value, "claimed_value").unwrap() + Scalar::from(1u64)),
+        );
+        assert!(!context
+            .verify_kzg_proof(commitment, input_point, wrong_value, proof)
+            .unwrap());
+    }
+
+    #[test]
+    fn compute_kzg_proof_inside_the_domain() {
+        let preset = Preset::new(16).unwrap();
+        let context = Context::new_insecure_with_preset(preset);
+        let blob_bytes = (0..16u64)
+            .flat_map(|i| scalar_to_bytes(&Scalar::from(i * i + 3)))
+            .collect();
+        let blob = Blob::from_bytes_with_preset(blob_bytes, preset).unwrap();
+        let commitment = context.blob_to_kzg_commitment(&blob).unwrap();
+
+        // The domain is in bit-reversed order, and opening at its element at `index`
+        // gives the field element at the same index in the blob
+        for (index, root) in context.domain.roots.iter().enumerate() {
+            let input_point = scalar_to_bytes(root);
+            let (proof, claimed_value) = context.compute_kzg_proof(&blob, input_point).unwrap();
+
+            assert_eq!(claimed_value, blob.as_bytes()[index * 32..(index + 1) * 32]);
+            assert!(context
+                .verify_kzg_proof(commitment, input_point, claimed_value, proof)
+                .unwrap());
+        }
+    }
+
+    #[test]
+    fn evaluate_blob_matches_proofs() {
+        let context = Context::new_insecure();
+        let blob = dummy_blob();
+
+        // Outside of the domain, the evaluation should match the value the proof is for
+        let input_point = scalar_to_bytes(&Scalar::from(123456789u64));
+        let (_, claimed_value) = context.compute_kzg_proof(&blob, input_point).unwrap();
+        assert_eq!(context.evaluate_blob(&blob, input_point), Ok(claimed_value));
+
+        // Inside of the domain, the evaluation is the field element at the same index
+        for index in [0, 1, 7, FIELD_ELEMENTS_PER_BLOB - 1] {
+            let input_point = scalar_to_bytes(&context.domain.roots[index]);
+            let expected = &blob.as_bytes()[index * 32..(index + 1) * 32];
+            assert_eq!(context.evaluate_blob(&blob, input_point).unwrap(), expected);
+        }
+
+        assert_eq!(
+            context.evaluate_blob(&blob, [0xff; 32]),
+            Err(KzgError::NonCanonicalScalar {
+                argument: "input_point"
+            })
+        );
+    }
+
+    #[test]
+    fn blob_kzg_proof_round_trip() {
+        let context = Context::new_insecure();
+        let blob = dummy_blob();
+
+        let commitment = context.blob_to_kzg_commitment(&blob).unwrap();
+        let proof = context.compute_blob_kzg_proof(&blob, commitment).unwrap();
+
+        assert!(context
+            .verify_blob_kzg_proof(&blob, commitment, proof)
+            .unwrap());
+
+        // The challenge is bound to the blob, so a proof for one blob
+        // should not verify for another
+        let other_blob = nth_dummy_blob(1);
+        let other_commitment = context.blob_to_kzg_commitment(&other_blob).unwrap();
+        assert!(!context
+            .verify_blob_kzg_proof(&other_blob, other_commitment, proof)
+            .unwrap());
+    }
+
+    #[test]
+    fn blobs_to_kzg_commitments() {
+        let context = Context::new_insecure_with_preset(Preset::MINIMAL);
+        let blobs: Vec<_> = (0..5u64)
+            .map(|i| {
+                let blob_bytes = (0..4u64)
+                    .flat_map(|j| scalar_to_bytes(&Scalar::from(i * 4 + j)))
+                    .collect();
+                Blob::from_bytes_with_preset(blob_bytes, Preset::MINIMAL).unwrap()
+            })
+            .collect();
+
+        // The commitments match committing to each blob individually, in order
+        let commitments = context.blobs_to_kzg_commitments(&blobs);
+        let expected: Vec<_> = blobs
+            .iter()
+            .map(|blob| context.blob_to_kzg_commitment(blob))
+            .collect();
+        assert_eq!(commitments, expected);
+        assert!(commitments.iter().all(Result::is_ok));
+
+        assert_eq!(context.blobs_to_kzg_commitments(&[]), Vec::new());
+
+        // Every invalid blob gets its own error, and the valid blobs are still committed to
+        let mut invalid_blobs = blobs.clone();
+        invalid_blobs[2] = dummy_blob();
+        invalid_blobs[4] = dummy_blob();
+        let invalid_length = Err(KzgError::InvalidBlobLength {
+            expected: Preset::MINIMAL.bytes_per_blob(),
+            got: constants::BYTES_PER_BLOB,
+        });
+        assert_eq!(
+            context.blobs_to_kzg_commitments(&invalid_blobs),
+            vec![
+                expected[0].clone(),
+                expected[1].clone(),
+                invalid_length.clone(),
+                expected[3].clone(),
+                invalid_length,
+            ]
+        );
+    }
+
+    #[test]
+    fn blob_kzg_proof_batch() {
+        let context = Context::new_insecure();
+        let num_blobs = 3;
+
+        let mut blobs = Vec::new();
+        let mut commitments = Vec::new();
+        let mut proofs = Vec::new();
+        for i in 0..num_blobs {
+            let blob = nth_dummy_blob(i);
+
+            let commitment = context.blob_to_kzg_commitment(&blob).unwrap();
+            let proof = context.compute_blob_kzg_proof(&blob, commitment).unwrap();
+
+            blobs.push(blob);
+            commitments.push(commitment);
+            proofs.push(proof);
+        }
+
+        assert!(context
+            .verify_blob_kzg_proof_batch(&blobs, &commitments, &proofs)
+            .unwrap());
+
+        // An empty batch is trivially valid
+        assert!(context.verify_blob_kzg_proof_batch(&[], &[], &[]).unwrap());
+
+        // Mismatched lengths are rejected
+        assert_eq!(
+            context.verify_blob_kzg_proof_batch(&blobs, &commitments, &proofs[1..]),
+            Err(KzgError::LengthMismatch {
+                argument: "proofs",
+                expected: num_blobs as usize,
+                got: num_blobs as usize - 1
+            })
+        );
+
+        // Errors report which item in the batch is invalid
+        let mut invalid_commitments = commitments.clone();
+        invalid_commitments[1] = [0; 48];
+        assert_eq!(
+            context.verify_blob_kzg_proof_batch(&blobs, &invalid_commitments, &proofs),
+            Err(KzgError::InvalidBatchElement {
+                index: 1,
+                error: Box::new(KzgError::InvalidPoint {
+                    argument: "commitment",
+                    error: PointDecodingError::InvalidEncoding
+                })
+            })
+        );
+
+        // Swapping two proofs should invalidate the batch
+        proofs.swap(0, 1);
+        assert!(!context
+            .verify_blob_kzg_proof_batch(&blobs, &commitments, &proofs)
+            .unwrap());
+    }
+
+    #[test]
+    fn aggregated_kzg_proof_round_trip() {
+        let context = Context::new_insecure();
+        let blobs: Vec<_> = (0..2).map(nth_dummy_blob).collect();
+
+        let (witness, commitments) = context.compute_aggregated_kzg_proof(&blobs).unwrap();
+        assert_eq!(
+            commitments[1],
+            context.blob_to_kzg_commitment(&blobs[1]).unwrap()
+        );
+
+        assert!(context
+            .verify_aggregated_kzg_proof(&blobs, &commitments, witness)
+            .unwrap());
+
+        let mut reversed_commitments = commitments;
+        reversed_commitments.reverse();
+        assert!(!context
+            .verify_aggregated_kzg_proof(&blobs, &reversed_commitments, witness)
+            .unwrap());
+    }
+
+    #[test]
+    fn aggregated_kzg_proof_rejects_empty_batch() {
+        let context = Context::new_insecure_with_preset(Preset::MINIMAL);
+
+        assert_eq!(
+            context.compute_aggregated_kzg_proof(&[]),
+            Err(KzgError::EmptyBatch)
+        );
+
+        let witness = G1Point::generator().to_compressed();
+        assert_eq!(
+            context.verify_aggregated_kzg_proof(&[], &[], witness),
+            Err(KzgError::EmptyBatch)
+        );
+    }
+
+    #[test]
+    fn commitments_against_versioned_hashes() {
+        let context = Context::new_insecure();
+        let commitments: Vec<_> = (0..3)
+            .map(|i| context.blob_to_kzg_commitment(&nth_dummy_blob(i)).unwrap())
+            .collect();
+        let versioned_hashes: Vec<_> = commitments.iter().map(kzg_to_versioned_hash).collect();
+
+        assert_eq!(
+            context.verify_commitments_against_versioned_hashes(&commitments, &versioned_hashes),
+            Ok(())
+        );
+
+        let mut swapped_hashes = versioned_hashes.clone();
+        swapped_hashes.swap(1, 2);
+        assert_eq!(
+            context.verify_commitments_against_versioned_hashes(&commitments, &swapped_hashes),
+            Err(KzgError::VersionedHashMismatch { index: 1 })
+        );
+
+        assert_eq!(
+            context
+                .verify_commitments_against_versioned_hashes(&commitments, &versioned_hashes[..2]),
+            Err(KzgError::LengthMismatch {
+                argument: "versioned_hashes",
+                expected: 3,
+                got: 2
+            })
+        );
+    }
+}
@@ -14,14 +14,8 @@ pub trait Permutable {
 impl Permutable for Domain {
     type PermutedType = Domain;
     fn permute(self) -> Self::PermutedType {
-        let permuted_roots = bit_reversal_permutation(&self.roots);
-        Domain {
-            roots: permuted_roots,
-            domain_size: self.domain_size,
-            domain_size_inv: self.domain_size_inv,
-            generator: self.generator,
-            generator_inv: self.generator_inv,
-        }
+        // The domain records that its roots are bit-reversed, so that the FFT follows their order
+        self.bit_reverse_roots()
     }
 }
 
@@ -54,6 +48,12 @@ fn min_num_bits_needed(num: u64) -> u32 {
 // Return a copy with bit-reversed permutation. This operation is idempotent.
 // Since this operation is done once at startup, we don't care about optimising it as much
 pub fn bit_reversal_permutation<T: Clone>(vector: &[T]) -> Vec<T> {
+    // A single element is its own permutation, and reversing zero bits
+    // would shift by the full width of the integer
+    if vector.len() <= 1 {
+        return vector.to_vec();
+    }
+
     (0..vector.len())
         .map(|i| reverse_bits(i as u64, vector.len() as u64))
         .map(|p_i| vector[p_i as usize].clone())
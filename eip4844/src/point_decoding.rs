@@ -0,0 +1,202 @@
+use crate::{PointDecodingError, SerializedG1Point};
+use crypto::G1Point;
+use group::prime::PrimeCurveAffine;
+
+/// Controls which G1 points `Context` accepts as commitments and proofs.
+///
+/// Points are always checked to be valid compressed encodings of points on the curve,
+/// and to be in the prime order subgroup. This policy only decides what happens
+/// when a point is the identity.
+///
+/// The consensus specs accept the identity for both commitments and proofs.
+/// The commitment to the zero polynomial is the identity, and so is the proof
+/// for any constant polynomial, so rejecting it would reject honest inputs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct G1DecodingPolicy {
+    /// Whether a commitment may be the identity
+    pub allow_identity_commitment: bool,
+    /// Whether a proof may be the identity
+    pub allow_identity_proof: bool,
+}
+
+impl G1DecodingPolicy {
+    /// The policy in the consensus specs, which accepts the identity everywhere
+    pub const SPEC: G1DecodingPolicy = G1DecodingPolicy {
+        allow_identity_commitment: true,
+        allow_identity_proof: true,
+    };
+
+    pub(crate) fn decode_commitment(
+        &self,
+        point_bytes: &SerializedG1Point,
+    ) -> Result<G1Point, PointDecodingError> {
+        decode_with_identity_check(point_bytes, self.allow_identity_commitment)
+    }
+
+    pub(crate) fn decode_proof(
+        &self,
+        point_bytes: &SerializedG1Point,
+    ) -> Result<G1Point, PointDecodingError> {
+        decode_with_identity_check(point_bytes, self.allow_identity_proof)
+    }
+}
+
+impl Default for G1DecodingPolicy {
+    fn default() -> Self {
+        G1DecodingPolicy::SPEC
+    }
+}
+
+fn decode_with_identity_check(
+    point_bytes: &SerializedG1Point,
+    allow_identity: bool,
+) -> Result<G1Point, PointDecodingError> {
+    let point = deserialize_g1_point(point_bytes)?;
+    if !allow_identity && bool::from(point.is_identity()) {
+        return Err(PointDecodingError::Identity);
+    }
+    Ok(point)
+}
+
+/// Decodes a compressed G1 point, checking that it is on the curve
+/// and in the prime order subgroup.
+pub(crate) fn deserialize_g1_point(
+    point_bytes: &SerializedG1Point,
+) -> Result<G1Point, PointDecodingError> {
+    let point = deserialize_g1_point_unchecked(point_bytes)?;
+    if !bool::from(point.is_torsion_free()) {
+        return Err(PointDecodingError::NotInSubgroup);
+    }
+    Ok(point)
+}
+
+/// Decodes a compressed G1 point, checking that it is on the curve,
+/// but not that it is in the prime order subgroup.
+///
+/// The subgroup check is the expensive part of decoding, so this is only
+/// used for points from a trusted source, such as a trusted setup file
+/// which has already been checked.
+pub(crate) fn deserialize_g1_point_unchecked(
+    point_bytes: &SerializedG1Point,
+) -> Result<G1Point, PointDecodingError> {
+    let ct_point = G1Point::from_compressed_unchecked(point_bytes);
+    if bool::from(ct_point.is_none()) {
+        return Err(PointDecodingError::InvalidEncoding);
+    }
+    Ok(ct_point.unwrap())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn identity() -> SerializedG1Point {
+        G1Point::identity().to_compressed()
+    }
+
+    // The point with x = 4 is on the curve, but not in the prime order subgroup
+    fn not_in_subgroup() -> SerializedG1Point {
+        let mut point = [0u8; 48];
+        point[0] = 0x80;
+        point[47] = 4;
+        point
+    }
+
+    #[test]
+    fn invalid_encodings_are_rejected() {
+        let generator = G1Point::generator().to_compressed();
+
+        // The compression flag is not set
+        let mut uncompressed_flag = generator;
+        uncompressed_flag[0] &= 0x7f;
+        // The point with x = 1 is not on the curve
+        let mut not_on_curve = [0u8; 48];
+        not_on_curve[0] = 0x80;
+        not_on_curve[47] = 1;
+        // The infinity flag is set, but the rest of the point is not zero
+        let mut non_zero_infinity = identity();
+        non_zero_infinity[47] = 1;
+        // The x-coordinate is not less than the field modulus
+        let mut x_too_large = [0xff; 48];
+        x_too_large[0] = 0x9f;
+
+        for point in [
+            uncompressed_flag,
+            not_on_curve,
+            non_zero_infinity,
+            x_too_large,
+        ] {
+            assert_eq!(
+                deserialize_g1_point(&point),
+                Err(PointDecodingError::InvalidEncoding)
+            );
+            assert_eq!(
+                deserialize_g1_point_unchecked(&point),
+                Err(PointDecodingError::InvalidEncoding)
+            );
+        }
+    }
+
+    #[test]
+    fn subgroup_check() {
+        assert_eq!(
+            deserialize_g1_point(&not_in_subgroup()),
+            Err(PointDecodingError::NotInSubgroup)
+        );
+        // The unchecked decoding only checks that the point is on the curve
+        assert!(deserialize_g1_point_unchecked(&not_in_subgroup()).is_ok());
+
+        let policy = G1DecodingPolicy::SPEC;
+        assert_eq!(
+            policy.decode_commitment(&not_in_subgroup()),
+            Err(PointDecodingError::NotInSubgroup)
+        );
+        assert_eq!(
+            policy.decode_proof(&not_in_subgroup()),
+            Err(PointDecodingError::NotInSubgroup)
+        );
+    }
+
+    #[test]
+    fn identity_policy() {
+        let spec = G1DecodingPolicy::default();
+        assert_eq!(spec, G1DecodingPolicy::SPEC);
+        assert_eq!(spec.decode_commitment(&identity()), Ok(G1Point::identity()));
+        assert_eq!(spec.decode_proof(&identity()), Ok(G1Point::identity()));
+
+        let no_identity_commitment = G1DecodingPolicy {
+            allow_identity_commitment: false,
+            ..G1DecodingPolicy::SPEC
+        };
+        assert_eq!(
+            no_identity_commitment.decode_commitment(&identity()),
+            Err(PointDecodingError::Identity)
+        );
+        assert!(no_identity_commitment.decode_proof(&identity()).is_ok());
+
+        let no_identity_proof = G1DecodingPolicy {
+            allow_identity_proof: false,
+            ..G1DecodingPolicy::SPEC
+        };
+        assert!(no_identity_proof.decode_commitment(&identity()).is_ok());
+        assert_eq!(
+            no_identity_proof.decode_proof(&identity()),
+            Err(PointDecodingError::Identity)
+        );
+
+        // Other points are not affected by the policy
+        let generator = G1Point::generator();
+        let strict = G1DecodingPolicy {
+            allow_identity_commitment: false,
+            allow_identity_proof: false,
+        };
+        assert_eq!(
+            strict.decode_commitment(&generator.to_compressed()),
+            Ok(generator)
+        );
+        assert_eq!(
+            strict.decode_proof(&generator.to_compressed()),
+            Ok(generator)
+        );
+    }
+}
@@ -0,0 +1,127 @@
+use crate::{
+    constants::{BLS_MODULUS, POINT_EVALUATION_INPUT_SIZE, POINT_EVALUATION_OUTPUT_SIZE},
+    kzg_to_versioned_hash, Context, KZGCommitmentBytes, KZGProofBytes, KzgError, SerializedScalar,
+    VersionedHash,
+};
+
+impl Context {
+    /// The point evaluation precompile at address `0x0A`.
+    ///
+    /// The input is `versioned_hash | z | y | commitment | proof`.
+    /// The commitment must hash to the versioned hash, and the proof must
+    /// show that the polynomial committed to evaluates to `y` at `z`.
+    ///
+    /// On success, returns the number of field elements per blob and `BLS_MODULUS`,
+    /// each encoded as a 32 byte big-endian integer.
+    pub fn point_evaluation_precompile(
+        &self,
+        input: &[u8],
+    ) -> Result<[u8; POINT_EVALUATION_OUTPUT_SIZE], KzgError> {
+        if input.len() != POINT_EVALUATION_INPUT_SIZE {
+            return Err(KzgError::InvalidPrecompileInputLength {
+                expected: POINT_EVALUATION_INPUT_SIZE,
+                got: input.len(),
+            });
+        }
+
+        let (versioned_hash, rest) = input.split_at(32);
+        let (z, rest) = rest.split_at(32);
+        let (y, rest) = rest.split_at(32);
+        let (commitment, proof) = rest.split_at(48);
+
+        // The lengths were checked above, so these conversions will not fail
+        let versioned_hash: &VersionedHash = versioned_hash.try_into().unwrap();
+        let z: SerializedScalar = z.try_into().unwrap();
+        let y: SerializedScalar = y.try_into().unwrap();
+        let commitment: KZGCommitmentBytes = commitment.try_into().unwrap();
+        let proof: KZGProofBytes = proof.try_into().unwrap();
+
+        if &kzg_to_versioned_hash(&commitment) != versioned_hash {
+            return Err(KzgError::PrecompileVersionedHashMismatch);
+        }
+
+        if !self.verify_kzg_proof(commitment, z, y, proof)? {
+            return Err(KzgError::InvalidProof);
+        }
+
+        let mut output = [0u8; POINT_EVALUATION_OUTPUT_SIZE];
+        let field_elements_per_blob = self.preset.field_elements_per_blob() as u64;
+        output[24..32].copy_from_slice(&field_elements_per_blob.to_be_bytes());
+        output[32..].copy_from_slice(&BLS_MODULUS);
+        Ok(output)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constants::BYTES_PER_BLOB;
+    use crate::Blob;
+
+    fn precompile_input(
+        versioned_hash: &[u8],
+        z: &[u8],
+        y: &[u8],
+        commitment: &[u8],
+        proof: &[u8],
+    ) -> Vec<u8> {
+        [versioned_hash, z, y, commitment, proof].concat()
+    }
+
+    #[test]
+    fn point_evaluation_precompile() {
+        let context = Context::new_insecure();
+
+        let mut blob_bytes = vec![0u8; BYTES_PER_BLOB];
+        for (i, chunk) in blob_bytes.chunks_exact_mut(32).enumerate() {
+            chunk[30..].copy_from_slice(&(i as u16).to_be_bytes());
+        }
+        let blob = Blob::from_bytes(blob_bytes).unwrap();
+        let commitment = context.blob_to_kzg_commitment(&blob).unwrap();
+        let versioned_hash = kzg_to_versioned_hash(&commitment);
+
+        let mut z = [0u8; 32];
+        z[31] = 42;
+        let (proof, y) = context.compute_kzg_proof(&blob, z).unwrap();
+
+        let input = precompile_input(&versioned_hash, &z, &y, &commitment, &proof);
+        let output = context.point_evaluation_precompile(&input).unwrap();
+        assert_eq!(
+            hex::encode(output),
+            "0000000000000000000000000000000000000000000000000000000000001000\
+             73eda753299d7d483339d80809a1d80553bda402fffe5bfeffffffff00000001"
+        );
+
+        assert_eq!(
+            context.point_evaluation_precompile(&input[1..]),
+            Err(KzgError::InvalidPrecompileInputLength {
+                expected: POINT_EVALUATION_INPUT_SIZE,
+                got: POINT_EVALUATION_INPUT_SIZE - 1
+            })
+        );
+
+        let mut wrong_hash = versioned_hash;
+        wrong_hash[31] ^= 1;
+        let input = precompile_input(&wrong_hash, &z, &y, &commitment, &proof);
+        assert_eq!(
+            context.point_evaluation_precompile(&input),
+            Err(KzgError::PrecompileVersionedHashMismatch)
+        );
+
+        let input = precompile_input(&versioned_hash, &z, &BLS_MODULUS, &commitment, &proof);
+        assert_eq!(
+            context.point_evaluation_precompile(&input),
+            Err(KzgError::NonCanonicalScalar {
+                argument: "claimed_value"
+            })
+        );
+
+        let mut wrong_y = y;
+        wrong_y[31] ^= 1;
+        let input = precompile_input(&versioned_hash, &z, &wrong_y, &commitment, &proof);
+        assert_eq!(
+            context.point_evaluation_precompile(&input),
+            Err(KzgError::InvalidProof)
+        );
+    }
+}
@@ -0,0 +1,104 @@
+use crate::{
+    constants::{FIELD_ELEMENTS_PER_BLOB, FIELD_ELEMENT_SIZE},
+    KzgError,
+};
+
+/// The number of field elements in each blob.
+///
+/// A `Context` is built for a single preset, and only accepts blobs of that size.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Preset {
+    field_elements_per_blob: usize,
+}
+
+// The largest power of two subgroup of the scalar field has size 2^32,
+// so the domain cannot be any larger than this
+const MAX_FIELD_ELEMENTS_PER_BLOB: u64 = 1 << 32;
+
+impl Preset {
+    /// The mainnet preset, with 4096 field elements per blob
+    pub const MAINNET: Preset = Preset {
+        field_elements_per_blob: FIELD_ELEMENTS_PER_BLOB,
+    };
+
+    /// The minimal preset used in the consensus spec tests, with 4 field elements per blob
+    pub const MINIMAL: Preset = Preset {
+        field_elements_per_blob: 4,
+    };
+
+    /// A preset with a custom number of field elements per blob.
+    ///
+    /// The number of field elements must be a power of two, since
+    /// the blob is interpreted as evaluations over a multiplicative subgroup.
+    pub fn new(field_elements_per_blob: usize) -> Result<Preset, KzgError> {
+        // The first bound is a u64, so that it does not overflow on 32 bit targets.
+        // On those targets, the second bound is smaller, and keeps `bytes_per_blob` from overflowing
+        if !field_elements_per_blob.is_power_of_two()
+            || field_elements_per_blob as u64 > MAX_FIELD_ELEMENTS_PER_BLOB
+            || field_elements_per_blob > usize::MAX / FIELD_ELEMENT_SIZE
+        {
+            return Err(KzgError::UnsupportedBlobSize {
+                field_elements_per_blob,
+            });
+        }
+
+        Ok(Preset {
+            field_elements_per_blob,
+        })
+    }
+
+    pub fn field_elements_per_blob(&self) -> usize {
+        self.field_elements_per_blob
+    }
+
+    pub fn bytes_per_blob(&self) -> usize {
+        self.field_elements_per_blob * FIELD_ELEMENT_SIZE
+    }
+}
+
+impl Default for Preset {
+    fn default() -> Self {
+        Preset::MAINNET
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn custom_presets() {
+        assert_eq!(Preset::new(4096), Ok(Preset::MAINNET));
+        assert_eq!(Preset::new(4), Ok(Preset::MINIMAL));
+        assert_eq!(Preset::new(1).unwrap().bytes_per_blob(), 32);
+
+        for field_elements_per_blob in [0, 3, 4095] {
+            assert_eq!(
+                Preset::new(field_elements_per_blob),
+                Err(KzgError::UnsupportedBlobSize {
+                    field_elements_per_blob
+                })
+            );
+        }
+    }
+
+    #[test]
+    fn largest_preset_does_not_overflow() {
+        // The largest power of two whose size in bytes fits in a usize.
+        // On 64 bit targets the size of the subgroup is smaller, and on 32 bit targets this is
+        let largest_by_size =
+            1usize << (usize::BITS - 1 - (usize::MAX / FIELD_ELEMENT_SIZE).leading_zeros());
+        let largest = MAX_FIELD_ELEMENTS_PER_BLOB.min(largest_by_size as u64) as usize;
+
+        let preset = Preset::new(largest).unwrap();
+        assert_eq!(preset.bytes_per_blob(), largest * FIELD_ELEMENT_SIZE);
+
+        let field_elements_per_blob = largest.wrapping_mul(2);
+        assert_eq!(
+            Preset::new(field_elements_per_blob),
+            Err(KzgError::UnsupportedBlobSize {
+                field_elements_per_blob
+            })
+        );
+    }
+}
@@ -0,0 +1,124 @@
+use crate::{Context, KzgError};
+use std::sync::{Arc, Mutex, OnceLock};
+
+// `Context` is only read from after it has been created, so it can be shared between threads.
+// This fails to compile if a field is ever added which would break that.
+const _: () = {
+    const fn assert_send_sync<T: Send + Sync>() {}
+    assert_send_sync::<Context>();
+};
+
+static GLOBAL_CONTEXT: OnceLock<Arc<Context>> = OnceLock::new();
+// Held while the global context is being created, so that `init` is only run once
+static GLOBAL_CONTEXT_INIT: Mutex<()> = Mutex::new(());
+
+impl Context {
+    /// Returns the process-wide context, creating it with `init` if this is the first call.
+    ///
+    /// `init` runs at most once, even if this is called from many threads at the same time.
+    /// If it returns an error, the error is returned and the next call will try again.
+    ///
+    /// Later calls return the same context, and ignore their `init`.
+    pub fn global_or_init(
+        init: impl FnOnce() -> Result<Context, KzgError>,
+    ) -> Result<Arc<Context>, KzgError> {
+        if let Some(context) = GLOBAL_CONTEXT.get() {
+            return Ok(context.clone());
+        }
+
+        // A panic in another caller's `init` does not leave the global in a bad state,
+        // so we can ignore the poisoning
+        let _guard = GLOBAL_CONTEXT_INIT
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+
+        // Another thread may have created the context while we were waiting
+        if let Some(context) = GLOBAL_CONTEXT.get() {
+            return Ok(context.clone());
+        }
+
+        let context = Arc::new(init()?);
+        // We hold the lock, so the global has not been set by anyone else
+        let _ = GLOBAL_CONTEXT.set(context.clone());
+        Ok(context)
+    }
+
+    /// Returns the process-wide context, if `Context::global_or_init` has created it
+    pub fn global() -> Option<Arc<Context>> {
+        GLOBAL_CONTEXT.get().cloned()
+    }
+
+    /// Wraps the context in an `Arc`, so that it can be shared between threads
+    pub fn into_shared(self) -> Arc<Context> {
+        Arc::new(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Preset;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    // This is the only test which touches the global context,
+    // since tests in this crate share a process
+    #[test]
+    fn global_context_is_created_once() {
+        assert!(Context::global().is_none());
+
+        let failed = Context::global_or_init(|| {
+            Err(KzgError::UnsupportedBlobSize {
+                field_elements_per_blob: 3,
+            })
+        });
+        assert!(failed.is_err());
+        assert!(Context::global().is_none());
+
+        let num_inits = AtomicUsize::new(0);
+        let contexts: Vec<_> = std::thread::scope(|scope| {
+            let handles: Vec<_> = (0..4)
+                .map(|_| {
+                    scope.spawn(|| {
+                        Context::global_or_init(|| {
+                            num_inits.fetch_add(1, Ordering::SeqCst);
+                            Ok(Context::new_insecure_with_preset(Preset::MINIMAL))
+                        })
+                        .unwrap()
+                    })
+                })
+                .collect();
+            handles
+                .into_iter()
+                .map(|handle| handle.join().unwrap())
+                .collect()
+        });
+
+        assert_eq!(num_inits.load(Ordering::SeqCst), 1);
+        let global = Context::global().unwrap();
+        for context in contexts {
+            assert!(Arc::ptr_eq(&context, &global));
+        }
+    }
+
+    #[test]
+    fn shared_context_can_be_used_from_many_threads() {
+        let context = Context::new_insecure_with_preset(Preset::MINIMAL).into_shared();
+        let blob = crate::Blob::from_bytes_with_preset(
+            vec![0; Preset::MINIMAL.bytes_per_blob()],
+            Preset::MINIMAL,
+        )
+        .unwrap();
+        let expected = context.blob_to_kzg_commitment(&blob).unwrap();
+
+        let handles: Vec<_> = (0..4)
+            .map(|_| {
+                let context = Arc::clone(&context);
+                let blob = blob.clone();
+                std::thread::spawn(move || context.blob_to_kzg_commitment(&blob).unwrap())
+            })
+            .collect();
+        for handle in handles {
+            assert_eq!(handle.join().unwrap(), expected);
+        }
+    }
+}
@@ -0,0 +1,406 @@
+use crate::{PointDecodingError, Preset, SerializedG1Point, SerializedG2Point};
+use crypto::{CommitKey, Domain, G1Point, G2Point};
+use serde::Deserialize;
+
+/// The trusted setup as it is distributed by the ceremony.
+///
+/// Each point is a hex encoded compressed group element.
+/// The `g1_lagrange` points are in their natural order,
+/// ie they have not been bit-reversed.
+#[derive(Debug, Deserialize)]
+pub(crate) struct TrustedSetup {
+    #[serde(default)]
+    g1_monomial: Option<Vec<String>>,
+    g1_lagrange: Vec<String>,
+    g2_monomial: Vec<String>,
+}
+
+/// The trusted setup, after the points have been decompressed and validated.
+pub(crate) struct ValidatedTrustedSetup {
+    pub(crate) g1_lagrange: Vec<G1Point>,
+    pub(crate) g2_monomial: Vec<G2Point>,
+}
+
+/// Errors that can occur when loading a trusted setup
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TrustedSetupError {
+    /// The input was not valid JSON, or did not contain the expected fields
+    InvalidJson(String),
+    /// The point at `index` in `field` was not valid hex
+    InvalidHex { field: &'static str, index: usize },
+    /// The point at `index` in `field` did not have the expected number of bytes
+    InvalidPointLength {
+        field: &'static str,
+        index: usize,
+        expected: usize,
+        got: usize,
+    },
+    /// The point at `index` in `field` was not on the curve, or not in the prime order subgroup
+    InvalidPoint { field: &'static str, index: usize },
+    /// `field` did not contain the expected number of points
+    UnexpectedNumberOfPoints {
+        field: &'static str,
+        expected: usize,
+        got: usize,
+    },
+    /// The lagrange points do not correspond to the monomial points
+    MonomialLagrangeMismatch,
+}
+
+impl std::fmt::Display for TrustedSetupError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TrustedSetupError::InvalidJson(reason) => {
+                write!(f, "trusted setup is not valid json: {reason}")
+            }
+            TrustedSetupError::InvalidHex { field, index } => {
+                write!(f, "point {index} in `{field}` is not valid hex")
+            }
+            TrustedSetupError::InvalidPointLength {
+                field,
+                index,
+                expected,
+                got,
+            } => write!(
+                f,
+                "point {index} in `{field}` has {got} bytes, expected {expected}"
+            ),
+            TrustedSetupError::InvalidPoint { field, index } => {
+                write!(f, "point {index} in `{field}` is not a valid group element")
+            }
+            TrustedSetupError::UnexpectedNumberOfPoints {
+                field,
+                expected,
+                got,
+            } => write!(f, "`{field}` has {got} points, expected {expected}"),
+            TrustedSetupError::MonomialLagrangeMismatch => write!(
+                f,
+                "the lagrange points do not correspond to the monomial points"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for TrustedSetupError {}
+
+// Decodes a G1 point in the trusted setup. Setups from a trusted source
+// may skip the subgroup check, see `Context::from_json_str_unchecked`.
+pub(crate) type DeserializeG1Point = fn(&SerializedG1Point) -> Result<G1Point, PointDecodingError>;
+
+// For 4844, we only need the generator of G2 and \tau times the generator
+pub(crate) const MIN_NUM_G2_POINTS: usize = 2;
+
+impl TrustedSetup {
+    pub(crate) fn from_json_str(json: &str) -> Result<TrustedSetup, TrustedSetupError> {
+        serde_json::from_str(json).map_err(|err| TrustedSetupError::InvalidJson(err.to_string()))
+    }
+
+    /// Decodes and validates every point in the trusted setup,
+    /// using `deserialize_g1` to decode the G1 points.
+    ///
+    /// The setup must have one G1 point for each field element in a blob of the given preset.
+    pub(crate) fn validate(
+        self,
+        preset: Preset,
+        deserialize_g1: DeserializeG1Point,
+    ) -> Result<ValidatedTrustedSetup, TrustedSetupError> {
+        let g1_monomial = self
+            .g1_monomial
+            .map(|points| decode_hex_points("g1_monomial", &points))
+            .transpose()?;
+        let g1_lagrange = decode_hex_points("g1_lagrange", &self.g1_lagrange)?;
+        let g2_monomial = decode_hex_points("g2_monomial", &self.g2_monomial)?;
+
+        ValidatedTrustedSetup::from_compressed(
+            g1_monomial.as_deref(),
+            &g1_lagrange,
+            &g2_monomial,
+            preset,
+            deserialize_g1,
+        )
+    }
+}
+
+impl ValidatedTrustedSetup {
+    /// Decompresses and validates every point in the trusted setup.
+    ///
+    /// If the monomial points are supplied, we also check that
+    /// the lagrange points were computed from them.
+    pub(crate) fn from_compressed(
+        g1_monomial: Option<&[SerializedG1Point]>,
+        g1_lagrange: &[SerializedG1Point],
+        g2_monomial: &[SerializedG2Point],
+        preset: Preset,
+        deserialize_g1: DeserializeG1Point,
+    ) -> Result<ValidatedTrustedSetup, TrustedSetupError> {
+        let num_g1_points = preset.field_elements_per_blob();
+        let g1_lagrange =
+            deserialize_points("g1_lagrange", g1_lagrange, num_g1_points, deserialize_g1)?;
+
+        if let Some(g1_monomial) = g1_monomial {
+            let g1_monomial =
+                deserialize_points("g1_monomial", g1_monomial, num_g1_points, deserialize_g1)?;
+            check_monomial_matches_lagrange(g1_monomial, &g1_lagrange)?;
+        }
+
+        if g2_monomial.len() < MIN_NUM_G2_POINTS {
+            return Err(TrustedSetupError::UnexpectedNumberOfPoints {
+                field: "g2_monomial",
+                expected: MIN_NUM_G2_POINTS,
+                got: g2_monomial.len(),
+            });
+        }
+        // Only the first two G2 points are used, so we do not decode the rest
+        let g2_monomial = deserialize_points(
+            "g2_monomial",
+            &g2_monomial[..MIN_NUM_G2_POINTS],
+            MIN_NUM_G2_POINTS,
+            deserialize_g2_point,
+        )?;
+
+        Ok(ValidatedTrustedSetup {
+            g1_lagrange,
+            g2_monomial,
+        })
+    }
+}
+
+// Converting the monomial points into lagrange form should give us the lagrange points.
+// If it does not, then the file is corrupted or the lagrange points are not in their natural order.
+fn check_monomial_matches_lagrange(
+    g1_monomial: Vec<G1Point>,
+    g1_lagrange: &[G1Point],
+) -> Result<(), TrustedSetupError> {
+    let domain = Domain::new(g1_monomial.len());
+    let expected_g1_lagrange = CommitKey::new(g1_monomial).into_lagrange(&domain);
+
+    if expected_g1_lagrange.inner != g1_lagrange {
+        return Err(TrustedSetupError::MonomialLagrangeMismatch);
+    }
+    Ok(())
+}
+
+fn decode_hex_points<const N: usize>(
+    field: &'static str,
+    points_hex: &[String],
+) -> Result<Vec<[u8; N]>, TrustedSetupError> {
+    points_hex
+        .iter()
+        .enumerate()
+        .map(|(index, point_hex)| {
+            let point_hex = point_hex.strip_prefix("0x").unwrap_or(point_hex);
+            let bytes = hex::decode(point_hex)
+                .map_err(|_| TrustedSetupError::InvalidHex { field, index })?;
+            bytes
+                .try_into()
+                .map_err(|bytes: Vec<u8>| TrustedSetupError::InvalidPointLength {
+                    field,
+                    index,
+                    expected: N,
+                    got: bytes.len(),
+                })
+        })
+        .collect()
+}
+
+fn deserialize_points<T, const N: usize>(
+    field: &'static str,
+    points: &[[u8; N]],
+    expected_num_points: usize,
+    deserialize: fn(&[u8; N]) -> Result<T, PointDecodingError>,
+) -> Result<Vec<T>, TrustedSetupError> {
+    if points.len() != expected_num_points {
+        return Err(TrustedSetupError::UnexpectedNumberOfPoints {
+            field,
+            expected: expected_num_points,
+            got: points.len(),
+        });
+    }
+
+    points
+        .iter()
+        .enumerate()
+        .map(|(index, point)| {
+            deserialize(point).map_err(|_| TrustedSetupError::InvalidPoint { field, index })
+        })
+        .collect()
+}
+
+fn deserialize_g2_point(point_bytes: &SerializedG2Point) -> Result<G2Point, PointDecodingError> {
+    let ct_point = G2Point::from_compressed(point_bytes);
+    if bool::from(ct_point.is_none()) {
+        return Err(PointDecodingError::InvalidEncoding);
+    }
+    Ok(ct_point.unwrap())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constants::FIELD_ELEMENTS_PER_BLOB;
+    use crate::point_decoding::{deserialize_g1_point, deserialize_g1_point_unchecked};
+    use group::prime::PrimeCurveAffine;
+
+    fn generator_hex_points(field_len: usize) -> Vec<String> {
+        let g1_hex = hex::encode(G1Point::generator().to_compressed());
+        vec![format!("0x{g1_hex}"); field_len]
+    }
+
+    fn trusted_setup_json(g1_lagrange: &[String], g2_monomial: &[String]) -> String {
+        serde_json::json!({
+            "g1_lagrange": g1_lagrange,
+            "g2_monomial": g2_monomial,
+        })
+        .to_string()
+    }
+
+    fn g2_hex_points() -> Vec<String> {
+        let g2_hex = hex::encode(G2Point::generator().to_compressed());
+        vec![format!("0x{g2_hex}"); MIN_NUM_G2_POINTS]
+    }
+
+    fn validate_json(json: &str) -> Result<ValidatedTrustedSetup, TrustedSetupError> {
+        TrustedSetup::from_json_str(json)?.validate(Preset::MAINNET, deserialize_g1_point)
+    }
+
+    #[test]
+    fn valid_setup() {
+        let json = trusted_setup_json(
+            &generator_hex_points(FIELD_ELEMENTS_PER_BLOB),
+            &g2_hex_points(),
+        );
+        let trusted_setup = validate_json(&json).unwrap();
+        assert_eq!(trusted_setup.g1_lagrange.len(), FIELD_ELEMENTS_PER_BLOB);
+    }
+
+    #[test]
+    fn number_of_points_follows_the_preset() {
+        let json = trusted_setup_json(&generator_hex_points(4), &g2_hex_points());
+        let trusted_setup = TrustedSetup::from_json_str(&json)
+            .unwrap()
+            .validate(Preset::MINIMAL, deserialize_g1_point)
+            .unwrap();
+        assert_eq!(trusted_setup.g1_lagrange.len(), 4);
+
+        assert_eq!(
+            validate_json(&json).err(),
+            Some(TrustedSetupError::UnexpectedNumberOfPoints {
+                field: "g1_lagrange",
+                expected: FIELD_ELEMENTS_PER_BLOB,
+                got: 4
+            })
+        );
+    }
+
+    #[test]
+    fn malformed_json() {
+        assert!(matches!(
+            validate_json("{\"g1_lagrange\": []"),
+            Err(TrustedSetupError::InvalidJson(_))
+        ));
+        // Missing the G2 points
+        assert!(matches!(
+            validate_json("{\"g1_lagrange\": []}"),
+            Err(TrustedSetupError::InvalidJson(_))
+        ));
+    }
+
+    #[test]
+    fn wrong_number_of_points() {
+        let json = trusted_setup_json(&generator_hex_points(16), &g2_hex_points());
+        assert_eq!(
+            validate_json(&json).err(),
+            Some(TrustedSetupError::UnexpectedNumberOfPoints {
+                field: "g1_lagrange",
+                expected: FIELD_ELEMENTS_PER_BLOB,
+                got: 16
+            })
+        );
+
+        let json = trusted_setup_json(
+            &generator_hex_points(FIELD_ELEMENTS_PER_BLOB),
+            &g2_hex_points()[..1],
+        );
+        assert_eq!(
+            validate_json(&json).err(),
+            Some(TrustedSetupError::UnexpectedNumberOfPoints {
+                field: "g2_monomial",
+                expected: MIN_NUM_G2_POINTS,
+                got: 1
+            })
+        );
+    }
+
+    #[test]
+    fn invalid_points() {
+        let mut g1_lagrange = generator_hex_points(FIELD_ELEMENTS_PER_BLOB);
+        g1_lagrange[10] = "0xzz".to_string();
+        let json = trusted_setup_json(&g1_lagrange, &g2_hex_points());
+        assert_eq!(
+            validate_json(&json).err(),
+            Some(TrustedSetupError::InvalidHex {
+                field: "g1_lagrange",
+                index: 10
+            })
+        );
+
+        let mut g1_lagrange = generator_hex_points(FIELD_ELEMENTS_PER_BLOB);
+        g1_lagrange[11] = "0xc0".to_string();
+        let json = trusted_setup_json(&g1_lagrange, &g2_hex_points());
+        assert_eq!(
+            validate_json(&json).err(),
+            Some(TrustedSetupError::InvalidPointLength {
+                field: "g1_lagrange",
+                index: 11,
+                expected: 48,
+                got: 1
+            })
+        );
+
+        // The x-coordinate 0x01 is not on the curve
+        let mut g1_lagrange = generator_hex_points(FIELD_ELEMENTS_PER_BLOB);
+        g1_lagrange[12] = format!("0x8{}1", "0".repeat(94));
+        let json = trusted_setup_json(&g1_lagrange, &g2_hex_points());
+        assert_eq!(
+            validate_json(&json).err(),
+            Some(TrustedSetupError::InvalidPoint {
+                field: "g1_lagrange",
+                index: 12
+            })
+        );
+    }
+
+    #[test]
+    fn unchecked_decoding_skips_the_subgroup_check() {
+        // The point (4, y) is on the curve, but not in the prime order subgroup
+        let mut g1_lagrange = generator_hex_points(FIELD_ELEMENTS_PER_BLOB);
+        g1_lagrange[13] = format!("0x8{}4", "0".repeat(94));
+        let json = trusted_setup_json(&g1_lagrange, &g2_hex_points());
+
+        assert_eq!(
+            validate_json(&json).err(),
+            Some(TrustedSetupError::InvalidPoint {
+                field: "g1_lagrange",
+                index: 13
+            })
+        );
+        let trusted_setup = TrustedSetup::from_json_str(&json)
+            .unwrap()
+            .validate(Preset::MAINNET, deserialize_g1_point_unchecked);
+        assert!(trusted_setup.is_ok());
+
+        // Points which are not on the curve are still rejected
+        g1_lagrange[13] = format!("0x8{}1", "0".repeat(94));
+        let json = trusted_setup_json(&g1_lagrange, &g2_hex_points());
+        let trusted_setup = TrustedSetup::from_json_str(&json)
+            .unwrap()
+            .validate(Preset::MAINNET, deserialize_g1_point_unchecked);
+        assert_eq!(
+            trusted_setup.err(),
+            Some(TrustedSetupError::InvalidPoint {
+                field: "g1_lagrange",
+                index: 13
+            })
+        );
+    }
+}
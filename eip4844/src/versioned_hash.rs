@@ -0,0 +1,32 @@
+use crate::{constants::VERSIONED_HASH_VERSION_KZG, KZGCommitmentBytes};
+use sha2::{Digest, Sha256};
+
+/// The hash of a commitment, as it appears in a blob transaction
+pub type VersionedHash = [u8; 32];
+
+/// Computes the versioned hash of a commitment.
+///
+/// This is the SHA-256 hash of the commitment, with the first byte
+/// replaced by `VERSIONED_HASH_VERSION_KZG`.
+pub fn kzg_to_versioned_hash(commitment: &KZGCommitmentBytes) -> VersionedHash {
+    let mut versioned_hash: VersionedHash = Sha256::digest(commitment).into();
+    versioned_hash[0] = VERSIONED_HASH_VERSION_KZG;
+    versioned_hash
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crypto::G1Point;
+    use group::prime::PrimeCurveAffine;
+
+    #[test]
+    fn versioned_hash_of_identity() {
+        // The compressed identity is 0xc0 followed by 47 zero bytes
+        let commitment = G1Point::identity().to_compressed();
+        let expected =
+            hex::decode("010657f37554c781402a22917dee2f75def7ab966d7b770905398eba3c444014")
+                .unwrap();
+        assert_eq!(kzg_to_versioned_hash(&commitment).to_vec(), expected);
+    }
+}
@@ -0,0 +1,172 @@
+use crate::{
+    bytes_to_scalar, scalar_to_bytes, Blob, Context, KZGCommitmentBytes, KZGProofBytes, KzgError,
+    Preset, SerializedScalar,
+};
+use crypto::{Polynomial, Proof, Scalar};
+
+/// Scratch space for the `_with_workspace` methods on `Context`.
+///
+/// Committing to a blob and creating proofs for it needs several buffers the size of a blob.
+/// These are kept in the workspace, so once it has been created, passing it to those
+/// methods again does not allocate.
+///
+/// It is used from threads in the same way as [`crypto::ProverWorkspace`].
+#[derive(Debug, Clone)]
+pub struct ProverWorkspace {
+    // Holds the evaluations of the blob which is being committed to or proved
+    evaluations: Vec<Scalar>,
+    inner: crypto::ProverWorkspace,
+}
+
+impl ProverWorkspace {
+    /// Creates a workspace for blobs of the given preset
+    pub fn new(preset: Preset) -> ProverWorkspace {
+        let field_elements_per_blob = preset.field_elements_per_blob();
+        ProverWorkspace {
+            evaluations: Vec::with_capacity(field_elements_per_blob),
+            inner: crypto::ProverWorkspace::new(field_elements_per_blob),
+        }
+    }
+
+    // Decodes the blob into the workspace, and calls `f` with its polynomial
+    fn with_polynomial<T>(
+        &mut self,
+        blob: &Blob,
+        f: impl FnOnce(&Polynomial, &mut crypto::ProverWorkspace) -> T,
+    ) -> T {
+        let polynomial = blob.to_polynomial_in(std::mem::take(&mut self.evaluations));
+        let result = f(&polynomial, &mut self.inner);
+        self.evaluations = polynomial.into_evaluations();
+        result
+    }
+}
+
+impl Context {
+    /// Creates a workspace for blobs of this context's preset
+    pub fn new_prover_workspace(&self) -> ProverWorkspace {
+        ProverWorkspace::new(self.preset)
+    }
+
+    /// Same as `Context::blob_to_kzg_commitment`, but does not allocate.
+    ///
+    /// The commitment is computed on the calling thread.
+    pub fn blob_to_kzg_commitment_with_workspace(
+        &self,
+        blob: &Blob,
+        workspace: &mut ProverWorkspace,
+    ) -> Result<KZGCommitmentBytes, KzgError> {
+        self.check_blob_size(blob)?;
+
+        let commit_key = &self.public_parameters.commit_key;
+        let commitment = workspace.with_polynomial(blob, |polynomial, workspace| {
+            commit_key.commit_with_workspace(polynomial, workspace)
+        });
+
+        Ok(commitment.to_compressed())
+    }
+
+    /// Same as `Context::compute_kzg_proof`, but does not allocate.
+    ///
+    /// The proof is computed on the calling thread.
+    pub fn compute_kzg_proof_with_workspace(
+        &self,
+        blob: &Blob,
+        input_point: SerializedScalar,
+        workspace: &mut ProverWorkspace,
+    ) -> Result<(KZGProofBytes, SerializedScalar), KzgError> {
+        self.check_blob_size(blob)?;
+        let input_point = bytes_to_scalar(&input_point, "input_point")?;
+
+        let commit_key = &self.public_parameters.commit_key;
+        let proof = workspace.with_polynomial(blob, |polynomial, workspace| {
+            let poly_comm = commit_key.commit_with_workspace(polynomial, workspace);
+            Proof::create_with_workspace(
+                commit_key,
+                polynomial,
+                poly_comm,
+                input_point,
+                &self.domain,
+                workspace,
+            )
+        });
+
+        Ok((
+            proof.quotient_commitment.to_compressed(),
+            scalar_to_bytes(&proof.output_point),
+        ))
+    }
+
+    /// Same as `Context::compute_blob_kzg_proof`, but does not allocate.
+    ///
+    /// The proof is computed on the calling thread.
+    pub fn compute_blob_kzg_proof_with_workspace(
+        &self,
+        blob: &Blob,
+        commitment: KZGCommitmentBytes,
+        workspace: &mut ProverWorkspace,
+    ) -> Result<KZGProofBytes, KzgError> {
+        self.check_blob_size(blob)?;
+        let poly_comm = self.bytes_to_commitment(&commitment)?;
+        let evaluation_challenge = crate::compute_challenge(blob, &commitment);
+
+        let proof = workspace.with_polynomial(blob, |polynomial, workspace| {
+            Proof::create_with_workspace(
+                &self.public_parameters.commit_key,
+                polynomial,
+                poly_comm,
+                evaluation_challenge,
+                &self.domain,
+                workspace,
+            )
+        });
+
+        Ok(proof.quotient_commitment.to_compressed())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn workspace_matches_allocating_methods() {
+        let preset = Preset::new(16).unwrap();
+        let context = Context::new_insecure_with_preset(preset);
+        let mut workspace = context.new_prover_workspace();
+
+        // The workspace is reused for several blobs
+        for i in 0..3u64 {
+            let blob_bytes = (0..16u64)
+                .flat_map(|j| scalar_to_bytes(&Scalar::from(i * 100 + j * j)))
+                .collect();
+            let blob = Blob::from_bytes_with_preset(blob_bytes, preset).unwrap();
+
+            let commitment = context.blob_to_kzg_commitment(&blob).unwrap();
+            assert_eq!(
+                context.blob_to_kzg_commitment_with_workspace(&blob, &mut workspace),
+                Ok(commitment)
+            );
+
+            let input_point = scalar_to_bytes(&Scalar::from(12345 + i));
+            assert_eq!(
+                context.compute_kzg_proof_with_workspace(&blob, input_point, &mut workspace),
+                context.compute_kzg_proof(&blob, input_point)
+            );
+
+            let proof = context
+                .compute_blob_kzg_proof_with_workspace(&blob, commitment, &mut workspace)
+                .unwrap();
+            assert_eq!(context.compute_blob_kzg_proof(&blob, commitment), Ok(proof));
+            assert!(context
+                .verify_blob_kzg_proof(&blob, commitment, proof)
+                .unwrap());
+        }
+
+        // Invalid inputs are rejected in the same way
+        let mainnet_blob = Blob::from_bytes(vec![0; crate::constants::BYTES_PER_BLOB]).unwrap();
+        assert_eq!(
+            context.blob_to_kzg_commitment_with_workspace(&mainnet_blob, &mut workspace),
+            context.blob_to_kzg_commitment(&mainnet_blob)
+        );
+    }
+}
@@ -0,0 +1,32 @@
+#!/usr/bin/env bash
+# Fetches the deneb KZG suite from a pinned consensus-spec-tests release,
+# and checks the release against a pinned hash before using it.
+set -euo pipefail
+
+VERSION="v1.4.0"
+URL="https://github.com/ethereum/consensus-spec-tests/releases/download/${VERSION}/general.tar.gz"
+# The sha256 of general.tar.gz for the release above.
+# It must be updated whenever VERSION changes
+SHA256=""
+
+if [ -z "$SHA256" ]; then
+    echo "No sha256 is pinned for consensus-spec-tests ${VERSION}" >&2
+    exit 1
+fi
+
+TESTS_DIR="$(cd "$(dirname "$0")" && pwd)"
+TMP_DIR="$(mktemp -d)"
+trap 'rm -rf "$TMP_DIR"' EXIT
+
+curl --fail --silent --show-error --location "$URL" --output "$TMP_DIR/general.tar.gz"
+echo "${SHA256}  $TMP_DIR/general.tar.gz" | sha256sum --check --status || {
+    echo "general.tar.gz does not match the pinned sha256 for consensus-spec-tests ${VERSION}" >&2
+    exit 1
+}
+tar --extract --gzip --file "$TMP_DIR/general.tar.gz" --directory "$TMP_DIR" tests/general/deneb/kzg
+
+rm -rf "$TESTS_DIR/general/deneb/kzg"
+mkdir -p "$TESTS_DIR/general/deneb"
+mv "$TMP_DIR/tests/general/deneb/kzg" "$TESTS_DIR/general/deneb/kzg"
+
+echo "Fetched the deneb KZG tests from consensus-spec-tests ${VERSION}"
@@ -0,0 +1,276 @@
+//! Runs the KZG reference tests from the consensus specs.
+//!
+//! The vectors are read from `tests/consensus-spec-tests/general/deneb/kzg`,
+//! which `tests/consensus-spec-tests/fetch.sh` fills from a pinned release.
+//! The tests fail if the vectors are missing.
+//!
+//! No release vectors are checked in yet, so the tests are ignored by default.
+//! Run them with `cargo test -p eip4844 --test consensus_spec -- --ignored`
+//! after running `fetch.sh`.
+//!
+//! An output of `null` means that the inputs are invalid, and the call should fail.
+use eip4844::{Blob, Context};
+use serde::{de::DeserializeOwned, Deserialize};
+use std::{
+    any::Any,
+    fmt::Debug,
+    panic::{self, AssertUnwindSafe},
+    path::{Path, PathBuf},
+    sync::OnceLock,
+};
+
+const TESTS_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/consensus-spec-tests");
+
+fn context() -> &'static Context {
+    static CONTEXT: OnceLock<Context> = OnceLock::new();
+    CONTEXT.get_or_init(|| {
+        let json = std::fs::read_to_string(Path::new(TESTS_DIR).join("trusted_setup.json"))
+            .expect("could not read the trusted setup");
+        Context::from_json_str(&json).expect("trusted setup is invalid")
+    })
+}
+
+#[derive(Deserialize)]
+struct TestVector<I, O> {
+    input: I,
+    output: Option<O>,
+}
+
+// The result of running a single vector
+enum Outcome {
+    Pass,
+    Fail(String),
+    Panic(String),
+}
+
+// Runs every vector for `handler`, each inside its own `catch_unwind`,
+// so that a panic in one vector does not stop the others from running.
+// The result of every vector is listed in the assertion message.
+//
+// `run` should return `None` if the inputs are invalid.
+fn run_handler<I, O>(handler: &str, run: impl Fn(&Context, I) -> Option<O>)
+where
+    I: DeserializeOwned,
+    O: DeserializeOwned + PartialEq + Debug,
+{
+    let context = context();
+    let cases = test_cases(handler);
+    assert!(!cases.is_empty(), "no test vectors found for {handler}");
+
+    let results: Vec<_> = cases
+        .iter()
+        .map(|case| {
+            let case_name = format!("{handler}/{}", case.file_name().unwrap().to_string_lossy());
+            let outcome = panic::catch_unwind(AssertUnwindSafe(|| {
+                let yaml = std::fs::read_to_string(case.join("data.yaml")).unwrap();
+                let vector: TestVector<I, O> = serde_yaml::from_str(&yaml)
+                    .unwrap_or_else(|err| panic!("could not parse data.yaml: {err}"));
+
+                let got = run(context, vector.input);
+                if got == vector.output {
+                    Outcome::Pass
+                } else {
+                    Outcome::Fail(format!("expected {:?}, got {got:?}", vector.output))
+                }
+            }))
+            .unwrap_or_else(|payload| Outcome::Panic(panic_message(payload.as_ref())));
+
+            (case_name, outcome)
+        })
+        .collect();
+
+    let num_passed = results
+        .iter()
+        .filter(|(_, outcome)| matches!(outcome, Outcome::Pass))
+        .count();
+    let report: Vec<_> = results
+        .iter()
+        .map(|(case_name, outcome)| match outcome {
+            Outcome::Pass => format!("PASS  {case_name}"),
+            Outcome::Fail(reason) => format!("FAIL  {case_name}: {reason}"),
+            Outcome::Panic(message) => format!("PANIC {case_name}: {message}"),
+        })
+        .collect();
+
+    assert!(
+        num_passed == results.len(),
+        "{} of {} vectors failed:\n{}",
+        results.len() - num_passed,
+        results.len(),
+        report.join("\n")
+    );
+}
+
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "unknown panic payload".to_string()
+    }
+}
+
+fn test_cases(handler: &str) -> Vec<PathBuf> {
+    let handler_dir = Path::new(TESTS_DIR)
+        .join("general/deneb/kzg")
+        .join(handler)
+        .join("kzg-mainnet");
+
+    let mut cases: Vec<_> = std::fs::read_dir(&handler_dir)
+        .unwrap_or_else(|err| {
+            panic!(
+                "could not read {}: {err}\nrun tests/consensus-spec-tests/fetch.sh to fetch the reference tests",
+                handler_dir.display()
+            )
+        })
+        .map(|entry| entry.unwrap().path())
+        .collect();
+    cases.sort();
+    cases
+}
+
+// The helpers below return `None` for inputs which cannot be decoded,
+// for example because they have the wrong length.
+
+fn decode_hex(hex_str: &str) -> Option<Vec<u8>> {
+    hex::decode(hex_str.strip_prefix("0x")?).ok()
+}
+
+fn decode_fixed<const N: usize>(hex_str: &str) -> Option<[u8; N]> {
+    decode_hex(hex_str)?.try_into().ok()
+}
+
+fn decode_blob(hex_str: &str) -> Option<Blob> {
+    Blob::from_bytes(decode_hex(hex_str)?).ok()
+}
+
+fn decode_all<T>(items: &[String], decode: fn(&str) -> Option<T>) -> Option<Vec<T>> {
+    items.iter().map(|item| decode(item)).collect()
+}
+
+fn encode_hex(bytes: &[u8]) -> String {
+    format!("0x{}", hex::encode(bytes))
+}
+
+#[derive(Deserialize)]
+struct BlobToKzgCommitmentInput {
+    blob: String,
+}
+
+#[test]
+#[ignore = "the release vectors are not checked in, run fetch.sh first"]
+fn blob_to_kzg_commitment() {
+    run_handler(
+        "blob_to_kzg_commitment",
+        |context, input: BlobToKzgCommitmentInput| {
+            let blob = decode_blob(&input.blob)?;
+            let commitment = context.blob_to_kzg_commitment(&blob).ok()?;
+            Some(encode_hex(&commitment))
+        },
+    );
+}
+
+#[derive(Deserialize)]
+struct ComputeKzgProofInput {
+    blob: String,
+    z: String,
+}
+
+#[test]
+#[ignore = "the release vectors are not checked in, run fetch.sh first"]
+fn compute_kzg_proof() {
+    run_handler(
+        "compute_kzg_proof",
+        |context, input: ComputeKzgProofInput| {
+            let blob = decode_blob(&input.blob)?;
+            let z = decode_fixed(&input.z)?;
+            let (proof, y) = context.compute_kzg_proof(&blob, z).ok()?;
+            Some(vec![encode_hex(&proof), encode_hex(&y)])
+        },
+    );
+}
+
+#[derive(Deserialize)]
+struct VerifyKzgProofInput {
+    commitment: String,
+    z: String,
+    y: String,
+    proof: String,
+}
+
+#[test]
+#[ignore = "the release vectors are not checked in, run fetch.sh first"]
+fn verify_kzg_proof() {
+    run_handler("verify_kzg_proof", |context, input: VerifyKzgProofInput| {
+        let commitment = decode_fixed(&input.commitment)?;
+        let z = decode_fixed(&input.z)?;
+        let y = decode_fixed(&input.y)?;
+        let proof = decode_fixed(&input.proof)?;
+        context.verify_kzg_proof(commitment, z, y, proof).ok()
+    });
+}
+
+#[derive(Deserialize)]
+struct ComputeBlobKzgProofInput {
+    blob: String,
+    commitment: String,
+}
+
+#[test]
+#[ignore = "the release vectors are not checked in, run fetch.sh first"]
+fn compute_blob_kzg_proof() {
+    run_handler(
+        "compute_blob_kzg_proof",
+        |context, input: ComputeBlobKzgProofInput| {
+            let blob = decode_blob(&input.blob)?;
+            let commitment = decode_fixed(&input.commitment)?;
+            let proof = context.compute_blob_kzg_proof(&blob, commitment).ok()?;
+            Some(encode_hex(&proof))
+        },
+    );
+}
+
+#[derive(Deserialize)]
+struct VerifyBlobKzgProofInput {
+    blob: String,
+    commitment: String,
+    proof: String,
+}
+
+#[test]
+#[ignore = "the release vectors are not checked in, run fetch.sh first"]
+fn verify_blob_kzg_proof() {
+    run_handler(
+        "verify_blob_kzg_proof",
+        |context, input: VerifyBlobKzgProofInput| {
+            let blob = decode_blob(&input.blob)?;
+            let commitment = decode_fixed(&input.commitment)?;
+            let proof = decode_fixed(&input.proof)?;
+            context.verify_blob_kzg_proof(&blob, commitment, proof).ok()
+        },
+    );
+}
+
+#[derive(Deserialize)]
+struct VerifyBlobKzgProofBatchInput {
+    blobs: Vec<String>,
+    commitments: Vec<String>,
+    proofs: Vec<String>,
+}
+
+#[test]
+#[ignore = "the release vectors are not checked in, run fetch.sh first"]
+fn verify_blob_kzg_proof_batch() {
+    run_handler(
+        "verify_blob_kzg_proof_batch",
+        |context, input: VerifyBlobKzgProofBatchInput| {
+            let blobs = decode_all(&input.blobs, decode_blob)?;
+            let commitments = decode_all(&input.commitments, decode_fixed)?;
+            let proofs = decode_all(&input.proofs, decode_fixed)?;
+            context
+                .verify_blob_kzg_proof_batch(&blobs, &commitments, &proofs)
+                .ok()
+        },
+    );
+}
@@ -0,0 +1,104 @@
+//! Checks that proving with a `ProverWorkspace` does not allocate once the workspace is warm.
+//!
+//! This is an integration test, since it replaces the global allocator.
+use eip4844::{constants::FIELD_ELEMENTS_PER_BLOB, Blob, Context};
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    cell::Cell,
+};
+
+// Counts the allocations made by the current thread,
+// so that tests running in parallel do not affect each other
+struct CountingAllocator;
+
+thread_local! {
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+fn count_allocation() {
+    // The thread local may already be destroyed if the thread is exiting
+    let _ = ALLOCATIONS.try_with(|allocations| allocations.set(allocations.get() + 1));
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        count_allocation();
+        System.alloc(layout)
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        count_allocation();
+        System.alloc_zeroed(layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        count_allocation();
+        System.realloc(ptr, layout, new_size)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+fn count_allocations<T>(f: impl FnOnce() -> T) -> (T, usize) {
+    let before = ALLOCATIONS.with(Cell::get);
+    let result = f();
+    (result, ALLOCATIONS.with(Cell::get) - before)
+}
+
+#[test]
+fn proving_with_a_workspace_does_not_allocate() {
+    let json = std::fs::read_to_string(concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/tests/consensus-spec-tests/trusted_setup.json"
+    ))
+    .unwrap();
+    let context = Context::from_json_str(&json).unwrap();
+
+    // The field elements are small, so they are canonical
+    let blob_bytes = (0..FIELD_ELEMENTS_PER_BLOB as u64)
+        .flat_map(|i| {
+            let mut field_element = [0u8; 32];
+            field_element[24..].copy_from_slice(&(i * i + 1).to_be_bytes());
+            field_element
+        })
+        .collect();
+    let blob = Blob::from_bytes(blob_bytes).unwrap();
+    let mut input_point = [0u8; 32];
+    input_point[31] = 123;
+
+    let mut workspace = context.new_prover_workspace();
+    let mut prove = || {
+        let commitment = context
+            .blob_to_kzg_commitment_with_workspace(&blob, &mut workspace)
+            .unwrap();
+        let kzg_proof = context
+            .compute_kzg_proof_with_workspace(&blob, input_point, &mut workspace)
+            .unwrap();
+        let blob_proof = context
+            .compute_blob_kzg_proof_with_workspace(&blob, commitment, &mut workspace)
+            .unwrap();
+        (commitment, kzg_proof, blob_proof)
+    };
+
+    // The workspace was sized for the preset when it was created,
+    // so even the first round of proving should not allocate
+    let (first, allocations) = count_allocations(&mut prove);
+    assert_eq!(allocations, 0);
+    let (second, allocations) = count_allocations(&mut prove);
+    assert_eq!(allocations, 0);
+    assert_eq!(first, second);
+
+    // Check that allocations are being counted
+    let (_, allocations) = count_allocations(|| context.blob_to_kzg_commitment(&blob).unwrap());
+    assert!(allocations > 0);
+
+    let (commitment, _, blob_proof) = first;
+    assert!(context
+        .verify_blob_kzg_proof(&blob, commitment, blob_proof)
+        .unwrap());
+}